@@ -1,25 +1,48 @@
 use crate::{
     errors::{error::ErrorBuilder, error_messages::invalid_if_statement_operator},
-    parser_lexer::lexer::Operators,
+    parser_lexer::{
+        lexer::{Operators, Span},
+        symbol::Symbol,
+    },
 };
 
 use super::{
+    expr::{BinOp, Expr, ExprKind, UnaryOp},
     function::Func,
+    struct_::Struct,
     use_::Use,
-    variable::{TypeVar, Variable},
+    variable::{Assign, TypeVar, Variable},
 };
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum TypesArg {
     String,
     Number,
+    Char,
+    /// A struct declared by the program, the symbol is the name of the struct
+    Struct(Symbol),
     None,
 }
 
+impl std::fmt::Display for TypesArg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypesArg::String => write!(f, "string"),
+            TypesArg::Number => write!(f, "number"),
+            TypesArg::Char => write!(f, "char"),
+            TypesArg::Struct(name) => write!(f, "{name}"),
+            TypesArg::None => write!(f, "none"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Arg {
-    pub value: String,
+    pub value: Symbol,
     pub type_: TypesArg,
+    pub span: Span,
+    /// The expression passed to a call, the arguments of a function declaration don't have one
+    pub expr: Option<Expr>,
 }
 
 impl From<TypesArg> for TypeVar {
@@ -27,7 +50,8 @@ impl From<TypesArg> for TypeVar {
         match val {
             TypesArg::String => TypeVar::String("".into()),
             TypesArg::Number => TypeVar::Number(0),
-            TypesArg::None => TypeVar::None,
+            TypesArg::Char => TypeVar::Char('\0'),
+            TypesArg::Struct(_) | TypesArg::None => TypeVar::None,
         }
     }
 }
@@ -35,11 +59,39 @@ impl From<TypesArg> for TypeVar {
 impl Arg {
     pub fn new() -> Arg {
         Arg {
-            value: String::new(),
+            value: Symbol::EMPTY,
             type_: TypesArg::None,
+            span: Span::default(),
+            expr: None,
         }
     }
-    pub fn assign_value(&mut self, value: String) -> Result<(), &'static str> {
+    /// Turns the expression passed to a call into a argument
+    ///
+    /// The expression is always kept, literals and identifiers are also stored in value so they
+    /// can be looked at without going through the expression.
+    pub fn from_expr(expr: Expr) -> Arg {
+        let mut arg = Arg::new();
+        arg.span = expr.span;
+        match &expr.kind {
+            ExprKind::Number(num) => {
+                arg.value = Symbol::intern(&num.to_string());
+                arg.type_ = TypesArg::Number;
+            }
+            ExprKind::String(string) => {
+                arg.value = Symbol::intern(string);
+                arg.type_ = TypesArg::String;
+            }
+            ExprKind::Char(char) => {
+                arg.value = Symbol::intern(&char.to_string());
+                arg.type_ = TypesArg::Char;
+            }
+            ExprKind::Identifier(id) => arg.value = *id,
+            _ => {}
+        }
+        arg.expr = Some(expr);
+        arg
+    }
+    pub fn assign_value(&mut self, value: Symbol) -> Result<(), &'static str> {
         if !self.value.is_empty() {
             return Err("This argument already has a value");
         }
@@ -54,31 +106,71 @@ impl Arg {
         Ok(())
     }
     pub fn clear_value(&mut self) {
-        self.value = "".into();
+        self.value = Symbol::EMPTY;
     }
     pub fn clear_type(&mut self) {
         self.type_ = TypesArg::None;
     }
+    /// Grows the span of the argument so it also covers the given span
+    pub fn extend_span(&mut self, span: Span) {
+        if self.span == Span::default() {
+            self.span = span;
+            return;
+        }
+        self.span = self.span.to(&span);
+    }
+    pub fn clear_span(&mut self) {
+        self.span = Span::default();
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum ReturnTypes {
     Number,
     String,
-    Identifier,
+    Char,
+    Struct(Symbol),
     None,
 }
 
+impl std::fmt::Display for ReturnTypes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReturnTypes::String => write!(f, "string"),
+            ReturnTypes::Number => write!(f, "number"),
+            ReturnTypes::Char => write!(f, "char"),
+            ReturnTypes::Struct(name) => write!(f, "{name}"),
+            ReturnTypes::None => write!(f, "none"),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Return {
-    pub value: String,
-    pub type_: ReturnTypes,
+    /// The returned value, `return;` doesn't have one
+    pub expr: Option<Expr>,
     pub line: usize,
+    pub span: Span,
+}
+
+impl Return {
+    pub fn new(expr: Option<Expr>, line: usize, span: Span) -> Return {
+        Return { expr, line, span }
+    }
+    /// Checks if the returned value uses a certain identifier
+    pub fn uses(&self, value: Symbol) -> bool {
+        self.expr.as_ref().is_some_and(|expr| expr.uses(value))
+    }
+    /// The functions called while calculating the returned value
+    pub fn calls(&self) -> Vec<&Func> {
+        self.expr.as_ref().map_or(Vec::new(), |expr| expr.calls())
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Case {
     EqEq(TypeVar, TypeVar),
+    NotEq(TypeVar, TypeVar),
     More(TypeVar, TypeVar),
     MoreEq(TypeVar, TypeVar),
     Less(TypeVar, TypeVar),
@@ -91,40 +183,281 @@ impl Case {
         match self {
             Case::MoreEq(_, _) => Case::MoreEq(one, two),
             Case::EqEq(_, _) => Case::EqEq(one, two),
+            Case::NotEq(_, _) => Case::NotEq(one, two),
             Case::More(_, _) => Case::More(one, two),
             Case::LessEq(_, _) => Case::LessEq(one, two),
             Case::Less(_, _) => Case::Less(one, two),
             Case::None => Case::None,
         }
     }
-    pub fn from_op(value: Operators) -> Result<Case, ErrorBuilder> {
+    /// The comparison a binary operator stands for, returns None if the operator doesn't compare
+    /// two values
+    pub fn from_bin_op(op: BinOp, one: TypeVar, two: TypeVar) -> Option<Case> {
+        let case = match op {
+            BinOp::EqEq => Case::EqEq(one, two),
+            BinOp::NotEq => Case::NotEq(one, two),
+            BinOp::Less => Case::Less(one, two),
+            BinOp::LessEq => Case::LessEq(one, two),
+            BinOp::More => Case::More(one, two),
+            BinOp::MoreEq => Case::MoreEq(one, two),
+            _ => return None,
+        };
+        Some(case)
+    }
+    /// Turns the comparison into a expression so it can be compiled like any other expression
+    pub fn to_expr(&self, span: Span) -> Option<Expr> {
+        let (op, one, two) = match self {
+            Case::EqEq(one, two) => (BinOp::EqEq, one, two),
+            Case::NotEq(one, two) => (BinOp::NotEq, one, two),
+            Case::More(one, two) => (BinOp::More, one, two),
+            Case::MoreEq(one, two) => (BinOp::MoreEq, one, two),
+            Case::Less(one, two) => (BinOp::Less, one, two),
+            Case::LessEq(one, two) => (BinOp::LessEq, one, two),
+            Case::None => return None,
+        };
+        Some(Expr::binary(op, one.to_expr(span)?, two.to_expr(span)?))
+    }
+    /// Checks if either side of the comparison uses a certain identifier
+    pub fn uses(&self, value: Symbol) -> bool {
+        match self {
+            Case::EqEq(one, two)
+            | Case::NotEq(one, two)
+            | Case::More(one, two)
+            | Case::MoreEq(one, two)
+            | Case::Less(one, two)
+            | Case::LessEq(one, two) => one.uses(value) || two.uses(value),
+            Case::None => false,
+        }
+    }
+    /// All function calls made on either side of the comparison
+    pub fn calls(&self) -> Vec<&Func> {
+        match self {
+            Case::EqEq(one, two)
+            | Case::NotEq(one, two)
+            | Case::More(one, two)
+            | Case::MoreEq(one, two)
+            | Case::Less(one, two)
+            | Case::LessEq(one, two) => {
+                let mut calls = one.calls();
+                calls.append(&mut two.calls());
+                calls
+            }
+            Case::None => Vec::new(),
+        }
+    }
+    pub fn from_op(value: Operators, span: Span) -> Result<Case, ErrorBuilder> {
         let value = match value {
             Operators::EqEq => Case::EqEq(TypeVar::None, TypeVar::None),
+            Operators::NotEq => Case::NotEq(TypeVar::None, TypeVar::None),
             Operators::Less => Case::Less(TypeVar::None, TypeVar::None),
             Operators::LessEq => Case::LessEq(TypeVar::None, TypeVar::None),
             Operators::More => Case::More(TypeVar::None, TypeVar::None),
             Operators::MoreEq => Case::MoreEq(TypeVar::None, TypeVar::None),
-            Operators::Invalid(invalid) => {
-                return Err(invalid_if_statement_operator(Operators::Invalid(invalid)))
-            }
-            Operators::Eq => return Err(invalid_if_statement_operator(Operators::Eq)),
+            // Assignments can't be used to compare values
+            op => return Err(invalid_if_statement_operator(op, span)),
         };
         Ok(value)
     }
 }
+/// The condition of a if statement
+///
+/// Comparisons joined by && and || are kept as a tree so the connectives aren't lost:
+///
+/// a > 1 && (b || !c) -> And(Case(a > 1), Or(Value(b), Not(Value(c))))
+#[derive(Debug, PartialEq, Clone)]
+pub enum Condition {
+    /// A comparison between two values: a > 1
+    Case(Case),
+    /// A single value used as a boolean, anything that isn't zero is true: if flag {}
+    Value(TypeVar),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+    Not(Box<Condition>),
+}
+
+impl Condition {
+    /// Builds the condition tree out of a parsed expression
+    pub fn from_expr(expr: Expr) -> Condition {
+        match expr.kind {
+            ExprKind::Binary {
+                op: BinOp::And,
+                lhs,
+                rhs,
+            } => Condition::And(
+                Box::new(Condition::from_expr(*lhs)),
+                Box::new(Condition::from_expr(*rhs)),
+            ),
+            ExprKind::Binary {
+                op: BinOp::Or,
+                lhs,
+                rhs,
+            } => Condition::Or(
+                Box::new(Condition::from_expr(*lhs)),
+                Box::new(Condition::from_expr(*rhs)),
+            ),
+            ExprKind::Unary {
+                op: UnaryOp::Not,
+                expr,
+            } => Condition::Not(Box::new(Condition::from_expr(*expr))),
+            ExprKind::Binary { op, lhs, rhs } if op.is_boolean() => {
+                let (one, two) = (TypeVar::from_expr(*lhs), TypeVar::from_expr(*rhs));
+                // Every boolean operator besides && || compares two values
+                Condition::Case(Case::from_bin_op(op, one, two).unwrap_or(Case::None))
+            }
+            kind => Condition::Value(TypeVar::from_expr(Expr::new(kind, expr.span))),
+        }
+    }
+    /// Checks if the condition uses a certain identifier anywhere within it
+    pub fn uses(&self, value: Symbol) -> bool {
+        match self {
+            Condition::Case(case) => case.uses(value),
+            Condition::Value(val) => val.uses(value),
+            Condition::And(one, two) | Condition::Or(one, two) => {
+                one.uses(value) || two.uses(value)
+            }
+            Condition::Not(condition) => condition.uses(value),
+        }
+    }
+    /// All function calls made anywhere within the condition
+    pub fn calls(&self) -> Vec<&Func> {
+        match self {
+            Condition::Case(case) => case.calls(),
+            Condition::Value(value) => value.calls(),
+            Condition::And(one, two) | Condition::Or(one, two) => {
+                let mut calls = one.calls();
+                calls.append(&mut two.calls());
+                calls
+            }
+            Condition::Not(condition) => condition.calls(),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Logic {
-    pub if_: Vec<Case>,
+    pub if_: Condition,
     pub do_: Box<Ast>,
+    /// Either a block for `else {}` or another [`Type::Logic`] for `else if ... {}`
     pub else_: Option<Box<Ast>>,
+    pub span: Span,
 }
 
 impl Logic {
-    pub fn new(case: Vec<Case>, else_: Option<Box<Ast>>, do_: Ast) -> Logic {
+    pub fn new(case: Condition, else_: Option<Box<Ast>>, do_: Ast, span: Span) -> Logic {
         Logic {
             if_: case,
             do_: Box::from(do_),
             else_,
+            span,
+        }
+    }
+    /// The if statement following the else: if a {} else if b {}
+    pub fn else_if(&self) -> Option<&Logic> {
+        match &self.else_.as_ref()?.type_ {
+            Type::Logic(logic) => Some(logic),
+            _ => None,
+        }
+    }
+    /// The bodies of every branch of a if else chain, including the last else
+    pub fn branches(&self) -> Vec<&Ast> {
+        let mut branches = vec![self.do_.as_ref()];
+        match (self.else_if(), &self.else_) {
+            (Some(else_if), _) => branches.append(&mut else_if.branches()),
+            (None, Some(else_)) => branches.push(else_),
+            (None, None) => (),
+        }
+        branches
+    }
+    /// Checks if any of the conditions of a if else chain uses a certain identifier
+    pub fn conditions_use(&self, value: Symbol) -> bool {
+        self.if_.uses(value)
+            || self
+                .else_if()
+                .is_some_and(|else_if| else_if.conditions_use(value))
+    }
+    /// All function calls made in any of the conditions of a if else chain
+    pub fn condition_calls(&self) -> Vec<&Func> {
+        let mut calls = self.if_.calls();
+        if let Some(else_if) = self.else_if() {
+            calls.append(&mut else_if.condition_calls());
+        }
+        calls
+    }
+}
+
+/// A loop that runs its body for as long as the condition is true
+///
+/// while i < 10 { ... }
+#[derive(Debug, PartialEq, Clone)]
+pub struct While {
+    pub condition: Condition,
+    pub body: Box<Ast>,
+    pub span: Span,
+}
+
+impl While {
+    pub fn new(condition: Condition, body: Ast, span: Span) -> While {
+        While {
+            condition,
+            body: Box::from(body),
+            span,
+        }
+    }
+}
+
+/// What a for loop iterates over
+#[derive(Debug, PartialEq, Clone)]
+pub enum ForIter {
+    /// start..end, start is included and end isn't
+    Range { start: Expr, end: Expr },
+    /// Any expression resulting in a array, such as a identifier or a array literal
+    Array(Expr),
+}
+
+impl ForIter {
+    /// Checks if the value iterated over uses a certain identifier
+    pub fn uses(&self, value: Symbol) -> bool {
+        match self {
+            ForIter::Range { start, end } => start.uses(value) || end.uses(value),
+            ForIter::Array(array) => array.uses(value),
+        }
+    }
+
+    /// All function calls made to get the value iterated over
+    pub fn calls(&self) -> Vec<&Func> {
+        match self {
+            ForIter::Range { start, end } => {
+                let mut calls = start.calls();
+                calls.append(&mut end.calls());
+                calls
+            }
+            ForIter::Array(array) => array.calls(),
+        }
+    }
+}
+
+/// A loop that runs its body once for every value in a range or array
+///
+/// for i in 0..10 { ... }
+///
+/// for x in arr { ... }
+///
+/// The variable only exists within the body of the loop.
+#[derive(Debug, PartialEq, Clone)]
+pub struct For {
+    pub var: Symbol,
+    pub iter: ForIter,
+    pub body: Box<Ast>,
+    pub span: Span,
+}
+
+impl For {
+    pub fn new(var: Symbol, iter: ForIter, body: Ast, span: Span) -> For {
+        For {
+            var,
+            iter,
+            body: Box::from(body),
+            span,
         }
     }
 }
@@ -135,6 +468,8 @@ pub enum Type {
     ConstVar(Variable),
     Variable(Variable),
     Function(Func),
+    /// A struct declaration, only allowed at the top level of a file
+    Struct(Struct),
     /// A call to a function
     //
     /// It contains the func that is being called under Func.name and the arguments passed into the
@@ -144,6 +479,14 @@ pub enum Type {
     Return(Return),
     Use(Use),
     Logic(Logic),
+    While(While),
+    For(For),
+    /// Stores a new value in a existing variable: a = 1; a += 1; arr[0] = 1;
+    Assign(Assign),
+    /// Leaves the loop it is written in
+    Break(Span),
+    /// Skips the rest of the body and starts the next iteration of the loop
+    Continue(Span),
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -162,14 +505,14 @@ impl Ast {
 }
 
 pub trait AstVar {
-    fn var_name(&self) -> Option<String>;
+    fn var_name(&self) -> Option<Symbol>;
     fn var_value(&self) -> Option<TypeVar>;
 }
 
 impl AstVar for Ast {
-    fn var_name(&self) -> Option<String> {
+    fn var_name(&self) -> Option<Symbol> {
         match &self.type_ {
-            Type::Variable(var) => Some(var.name.clone()),
+            Type::Variable(var) => Some(var.name),
             _ => None,
         }
     }
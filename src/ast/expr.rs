@@ -0,0 +1,223 @@
+use crate::parser_lexer::{
+    lexer::{Operators, Span, TokenType},
+    symbol::Symbol,
+};
+
+use super::function::Func;
+
+/// The operators that can be used in between two expressions
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BinOp {
+    Or,
+    And,
+    EqEq,
+    NotEq,
+    Less,
+    LessEq,
+    More,
+    MoreEq,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl BinOp {
+    /// How strong the operator binds, operators with a higher precedence are evaluated first.
+    ///
+    /// - || -> 1
+    /// - && -> 2
+    /// - == != -> 3
+    /// - < <= > >= -> 4
+    /// - + - -> 5
+    /// - * / % -> 6
+    pub fn precedence(self) -> u8 {
+        match self {
+            BinOp::Or => 1,
+            BinOp::And => 2,
+            BinOp::EqEq | BinOp::NotEq => 3,
+            BinOp::Less | BinOp::LessEq | BinOp::More | BinOp::MoreEq => 4,
+            BinOp::Add | BinOp::Sub => 5,
+            BinOp::Mul | BinOp::Div | BinOp::Rem => 6,
+        }
+    }
+
+    /// The binary operator a token stands for, returns None if the token isn't a binary operator
+    pub fn from_token(token: &TokenType) -> Option<BinOp> {
+        let op = match token {
+            TokenType::OrOr => BinOp::Or,
+            TokenType::AndAnd => BinOp::And,
+            TokenType::Operator(Operators::EqEq) => BinOp::EqEq,
+            TokenType::Operator(Operators::NotEq) => BinOp::NotEq,
+            TokenType::Operator(Operators::Less) => BinOp::Less,
+            TokenType::Operator(Operators::LessEq) => BinOp::LessEq,
+            TokenType::Operator(Operators::More) => BinOp::More,
+            TokenType::Operator(Operators::MoreEq) => BinOp::MoreEq,
+            TokenType::Plus => BinOp::Add,
+            TokenType::Min => BinOp::Sub,
+            TokenType::Star => BinOp::Mul,
+            TokenType::Slash => BinOp::Div,
+            TokenType::Percent => BinOp::Rem,
+            _ => return None,
+        };
+        Some(op)
+    }
+
+    /// The operator a compound assignment such as += applies, returns None for any operator
+    /// that isn't a compound assignment
+    pub fn from_compound(op: &Operators) -> Option<BinOp> {
+        let op = match op {
+            Operators::PlusEq => BinOp::Add,
+            Operators::MinEq => BinOp::Sub,
+            Operators::StarEq => BinOp::Mul,
+            Operators::SlashEq => BinOp::Div,
+            Operators::PercentEq => BinOp::Rem,
+            _ => return None,
+        };
+        Some(op)
+    }
+
+    /// Comparisons and the logical operators result in a boolean instead of a number
+    pub fn is_boolean(self) -> bool {
+        self.precedence() <= BinOp::Less.precedence()
+    }
+}
+
+/// The operators that are written in front of a expression
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum UnaryOp {
+    /// -value
+    Neg,
+    /// !value
+    Not,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ExprKind {
+    Number(i32),
+    String(String),
+    Char(char),
+    Bool(bool),
+    Identifier(Symbol),
+    /// A call to a function, the name and the arguments are stored like they are for
+    /// [`super::ast::Type::FunctionCall`]
+    Call(Func),
+    Array(Vec<Expr>),
+    /// The value at a index of a array: arr[i]
+    Index {
+        array: Box<Expr>,
+        index: Box<Expr>,
+    },
+    /// A new value of a struct, the fields are kept in the order they are written in:
+    /// Point { x: 1, y: 2 }
+    StructLit {
+        name: Symbol,
+        fields: Vec<(Symbol, Expr)>,
+    },
+    /// The value of a field of a struct: p.x
+    Field {
+        value: Box<Expr>,
+        field: Symbol,
+    },
+    Binary {
+        op: BinOp,
+        lhs: Box<Expr>,
+        rhs: Box<Expr>,
+    },
+    Unary {
+        op: UnaryOp,
+        expr: Box<Expr>,
+    },
+}
+
+/// A expression such as `a + 1`, `-(b * 2)` or `add(a, b) > 10`
+///
+/// Parentheses don't get their own node, they only change the shape of the tree.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub span: Span,
+}
+
+impl Expr {
+    pub fn new(kind: ExprKind, span: Span) -> Expr {
+        Expr { kind, span }
+    }
+
+    pub fn binary(op: BinOp, lhs: Expr, rhs: Expr) -> Expr {
+        let span = lhs.span.to(&rhs.span);
+        Expr::new(
+            ExprKind::Binary {
+                op,
+                lhs: Box::new(lhs),
+                rhs: Box::new(rhs),
+            },
+            span,
+        )
+    }
+
+    pub fn unary(op: UnaryOp, expr: Expr, span: Span) -> Expr {
+        Expr::new(
+            ExprKind::Unary {
+                op,
+                expr: Box::new(expr),
+            },
+            span,
+        )
+    }
+
+    /// The variable a field access or index starts from: `a` for a.b[0].c
+    ///
+    /// Returns None if the expression doesn't start from a variable.
+    pub fn root(&self) -> Option<Symbol> {
+        match &self.kind {
+            ExprKind::Identifier(id) => Some(*id),
+            ExprKind::Index { array: value, .. } | ExprKind::Field { value, .. } => value.root(),
+            _ => None,
+        }
+    }
+
+    /// Checks if the expression uses a certain identifier anywhere within it
+    pub fn uses(&self, value: Symbol) -> bool {
+        match &self.kind {
+            ExprKind::Identifier(id) => *id == value,
+            ExprKind::Call(call) => call.args_uses(value),
+            ExprKind::Array(values) => values.iter().any(|expr| expr.uses(value)),
+            ExprKind::Index { array, index } => array.uses(value) || index.uses(value),
+            ExprKind::StructLit { fields, .. } => fields.iter().any(|(_, expr)| expr.uses(value)),
+            ExprKind::Field { value: expr, .. } => expr.uses(value),
+            ExprKind::Binary { lhs, rhs, .. } => lhs.uses(value) || rhs.uses(value),
+            ExprKind::Unary { expr, .. } => expr.uses(value),
+            _ => false,
+        }
+    }
+
+    /// All function calls made within the expression
+    pub fn calls(&self) -> Vec<&Func> {
+        match &self.kind {
+            ExprKind::Call(call) => {
+                let mut calls = vec![call];
+                calls.append(&mut call.arg_calls());
+                calls
+            }
+            ExprKind::Array(values) => values.iter().flat_map(|expr| expr.calls()).collect(),
+            ExprKind::Index { array, index } => {
+                let mut calls = array.calls();
+                calls.append(&mut index.calls());
+                calls
+            }
+            ExprKind::StructLit { fields, .. } => {
+                fields.iter().flat_map(|(_, expr)| expr.calls()).collect()
+            }
+            ExprKind::Field { value, .. } => value.calls(),
+            ExprKind::Binary { lhs, rhs, .. } => {
+                let mut calls = lhs.calls();
+                calls.append(&mut rhs.calls());
+                calls
+            }
+            ExprKind::Unary { expr, .. } => expr.calls(),
+            _ => Vec::new(),
+        }
+    }
+}
@@ -1,14 +1,18 @@
 use crate::ast::ast::{Arg, Ast, ReturnTypes};
+use crate::parser_lexer::{lexer::Span, symbol::Symbol};
 
 use super::ast::TypesArg;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Func {
-    pub name: String,
+    pub name: Symbol,
     pub args: Vec<Arg>,
     pub body: Option<Box<Ast>>,
     pub return_type: ReturnTypes,
     pub line: usize,
+    pub span: Span,
+    /// The /// doc comments written above the function, one entry per line
+    pub docs: Vec<String>,
 }
 
 impl Func {
@@ -20,7 +24,7 @@ impl Func {
         Some(arg)
     }
 
-    pub fn get_arg_index_(&self, value: &str) -> Option<u32> {
+    pub fn get_arg_index_(&self, value: Symbol) -> Option<u32> {
         for (i, arg) in self.args.iter().enumerate() {
             if arg.value == value {
                 return Some(i as u32);
@@ -29,13 +33,20 @@ impl Func {
         None
     }
 
-    /// Checks if args uses a certaint value only works for identifiers
-    pub fn args_uses(&self, value: &str) -> bool {
-        for arg in &self.args {
-            if arg.type_ == TypesArg::None && arg.value == value {
-                return true;
-            }
-        }
-        false
+    /// Checks if args uses a certaint value, either as a identifier or within a expression
+    pub fn args_uses(&self, value: Symbol) -> bool {
+        self.args.iter().any(|arg| match &arg.expr {
+            Some(expr) => expr.uses(value),
+            None => arg.type_ == TypesArg::None && arg.value == value,
+        })
+    }
+
+    /// All the function calls made within the arguments of this function call
+    pub fn arg_calls(&self) -> Vec<&Func> {
+        self.args
+            .iter()
+            .filter_map(|arg| arg.expr.as_ref())
+            .flat_map(|expr| expr.calls())
+            .collect()
     }
 }
@@ -1,4 +1,6 @@
 pub mod ast;
+pub mod expr;
 pub mod function;
+pub mod struct_;
 pub mod use_;
 pub mod variable;
@@ -0,0 +1,41 @@
+use crate::parser_lexer::{lexer::Span, symbol::Symbol};
+
+use super::ast::TypesArg;
+
+/// A field of a struct declaration: x: number
+#[derive(Debug, PartialEq, Clone)]
+pub struct Field {
+    pub name: Symbol,
+    pub type_: TypesArg,
+    pub span: Span,
+}
+
+/// A user defined type made out of named fields
+///
+/// struct Point { x: number, y: number }
+#[derive(Debug, PartialEq, Clone)]
+pub struct Struct {
+    pub name: Symbol,
+    pub fields: Vec<Field>,
+    pub span: Span,
+    /// The /// doc comments written above the struct, one entry per line
+    pub docs: Vec<String>,
+}
+
+impl Struct {
+    pub fn new(name: Symbol, fields: Vec<Field>, span: Span, docs: Vec<String>) -> Struct {
+        Struct {
+            name,
+            fields,
+            span,
+            docs,
+        }
+    }
+
+    /// The position of a field within the struct, the fields are laid out in the order they are
+    /// written in.
+    pub fn field_index(&self, name: Symbol) -> Option<u32> {
+        let index = self.fields.iter().position(|field| field.name == name)?;
+        Some(index as u32)
+    }
+}
@@ -2,14 +2,14 @@ use std::{fs, path::PathBuf};
 
 use crate::{
     errors::error::{BuildError, ErrorBuilder},
-    parser_lexer::lexer::{Lexer, Token},
+    parser_lexer::lexer::{LexError, Lexer, Span, Token},
 };
 
 use super::ast::{Ast, Type};
 
-/// The use struct containing the file name of the use
+/// The use struct containing the file name of the use and where it was found
 #[derive(Clone, Debug, PartialEq)]
-pub struct Use(String);
+pub struct Use(String, Span);
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Used(Vec<Use>);
@@ -18,20 +18,22 @@ pub struct Used(Vec<Use>);
 pub struct UsedCompiled {
     pub file_name: String,
     pub contents: Vec<Token>,
+    pub errors: Vec<LexError>,
 }
 
 impl UsedCompiled {
-    fn new(file_name: String, contents: Vec<Token>) -> UsedCompiled {
+    fn new(file_name: String, contents: Vec<Token>, errors: Vec<LexError>) -> UsedCompiled {
         UsedCompiled {
             file_name,
             contents,
+            errors,
         }
     }
 }
 
 impl Use {
-    pub fn new(file_name: String) -> Use {
-        Use(file_name)
+    pub fn new(file_name: String, span: Span) -> Use {
+        Use(file_name, span)
     }
 
     pub fn valide_ext(&self) -> bool {
@@ -63,7 +65,7 @@ pub trait GetUses {
             match &ast.type_ {
                 Type::Use(use_) => {
                     let true = use_.validate() else {
-                    return Err(ErrorBuilder::new().message(format!("Found a invalid use: {};", use_.0)).helper("consider changing the extension of the file to .lp or bringing it into scope.").build_error())
+                    return Err(ErrorBuilder::new().message(format!("Found a invalid use: {};", use_.0)).helper("consider changing the extension of the file to .lp or bringing it into scope.").span(use_.1).build_error())
                 };
                     uses.push(use_.clone());
                 }
@@ -94,8 +96,8 @@ impl CompileUses for Used {
         let mut compile_used = Vec::new();
         for file in &self.0 {
             let string = fs::read_to_string(&file.0).unwrap();
-            let lexed = Token::lex(string);
-            compile_used.push(UsedCompiled::new(file.0.clone(), lexed));
+            let (lexed, errors) = Token::lex_file(&file.0, string);
+            compile_used.push(UsedCompiled::new(file.0.clone(), lexed, errors));
         }
         Ok(compile_used)
     }
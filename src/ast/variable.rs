@@ -1,7 +1,11 @@
 use crate::errors::error::BuildError;
 use crate::errors::error::ErrorBuilder;
+use crate::errors::error_messages::number_out_of_range;
+use crate::parser_lexer::lexer::Span;
+use crate::parser_lexer::symbol::Symbol;
 
 use super::ast::TypesArg;
+use super::expr::{BinOp, Expr, ExprKind};
 use super::function::Func;
 
 #[derive(Debug, PartialEq, Clone)]
@@ -9,8 +13,11 @@ pub enum TypeVar {
     Arr { values: Vec<TypeVar> },
     Number(i32),
     String(String),
-    Identifier(String),
+    Char(char),
+    Identifier(Symbol),
     FunctionCall(Func),
+    /// Any value that isn't a single literal, identifier or call, such as `a + b * 2`
+    Expr(Box<Expr>),
     None,
 }
 
@@ -18,11 +25,95 @@ impl TypeVar {
     pub fn is_none(&self) -> bool {
         self == &TypeVar::None
     }
-    pub fn parse_number(num: String) -> Self {
-        let num = num.parse().unwrap();
-        Self::Number(num)
+    /// Parses a number token, returns a error if the number doesn't fit in a number
+    pub fn parse_number(num: Symbol, span: Span) -> Result<Self, ErrorBuilder> {
+        match Self::parse_int(num.as_str()) {
+            Some(num) => Ok(Self::Number(num)),
+            None => Err(number_out_of_range(num, span)),
+        }
+    }
+    /// Parses a number literal the way the lexer writes them, with a optional 0x, 0b or 0o
+    /// prefix. A leading - is allowed so negated literals can be parsed as well.
+    ///
+    /// Returns None if the literal isn't a valid number or doesn't fit in a i32.
+    pub fn parse_int(num: &str) -> Option<i32> {
+        if let Some(num) = num.strip_prefix('-') {
+            // Parsed as a i64 first, -2147483648 fits in a i32 but 2147483648 doesn't
+            let num = -i64::from(Self::parse_unsigned(num)?);
+            return i32::try_from(num).ok();
+        }
+        i32::try_from(Self::parse_unsigned(num)?).ok()
+    }
+    fn parse_unsigned(num: &str) -> Option<u32> {
+        let (digits, radix) = match num.get(..2) {
+            Some("0x") => (&num[2..], 16),
+            Some("0b") => (&num[2..], 2),
+            Some("0o") => (&num[2..], 8),
+            _ => (num, 10),
+        };
+        u32::from_str_radix(&digits.replace('_', ""), radix).ok()
+    }
+    /// Turns the value back into a expression so it can be compiled by the expression compiler,
+    /// returns None if there is no value.
+    pub fn to_expr(&self, span: Span) -> Option<Expr> {
+        let kind = match self {
+            TypeVar::Number(num) => ExprKind::Number(*num),
+            TypeVar::String(string) => ExprKind::String(string.clone()),
+            TypeVar::Char(char) => ExprKind::Char(*char),
+            TypeVar::Identifier(id) => ExprKind::Identifier(*id),
+            TypeVar::FunctionCall(call) => ExprKind::Call(call.clone()),
+            TypeVar::Arr { values } => ExprKind::Array(
+                values
+                    .iter()
+                    .map(|value| value.to_expr(span))
+                    .collect::<Option<_>>()?,
+            ),
+            TypeVar::Expr(expr) => return Some(*expr.clone()),
+            TypeVar::None => return None,
+        };
+        Some(Expr::new(kind, span))
+    }
+    /// Converts a parsed expression into the value of a variable
+    ///
+    /// Literals, identifiers, calls and arrays keep their own variant so the rest of the compiler
+    /// can keep handling them the way it did, only compound expressions become a [`TypeVar::Expr`].
+    pub fn from_expr(expr: Expr) -> Self {
+        match expr.kind {
+            ExprKind::Number(num) => Self::Number(num),
+            ExprKind::String(string) => Self::String(string),
+            ExprKind::Char(char) => Self::Char(char),
+            ExprKind::Identifier(id) => Self::Identifier(id),
+            ExprKind::Call(call) => Self::FunctionCall(call),
+            ExprKind::Array(values) => Self::Arr {
+                values: values.into_iter().map(Self::from_expr).collect(),
+            },
+            _ => Self::Expr(Box::new(expr)),
+        }
     }
-    pub fn uses(&self, uses: &str) -> bool {
+    /// The type of a literal value, anything else only gets its type once it is compiled
+    pub fn literal_type(&self) -> Option<TypesArg> {
+        match self {
+            TypeVar::Number(_) => Some(TypesArg::Number),
+            TypeVar::String(_) => Some(TypesArg::String),
+            TypeVar::Char(_) => Some(TypesArg::Char),
+            _ => None,
+        }
+    }
+    /// The value a variable with a type but without a value starts with: let count: number;
+    pub fn default_of(type_: &TypesArg) -> TypeVar {
+        match type_ {
+            TypesArg::Number => TypeVar::Number(0),
+            TypesArg::String => TypeVar::String(String::new()),
+            TypesArg::Char => TypeVar::Char('\0'),
+            // A struct without a value starts out zeroed once it is compiled
+            TypesArg::Struct(_) | TypesArg::None => TypeVar::None,
+        }
+    }
+    /// Chars are lexed as a string containing the single char
+    pub fn parse_char(char: &str) -> Self {
+        Self::Char(char.chars().next().unwrap_or('\0'))
+    }
+    pub fn uses(&self, uses: Symbol) -> bool {
         match &self {
             TypeVar::Identifier(value) if *value == uses => true,
             TypeVar::Arr { values } => {
@@ -33,25 +124,36 @@ impl TypeVar {
                 }
                 false
             }
-            TypeVar::FunctionCall(func) => {
-                let args = &func.args;
-                for arg in args {
-                    if arg.type_ == TypesArg::None && arg.value == uses {
-                        return true;
-                    }
-                }
-                false
-            }
+            TypeVar::FunctionCall(func) => func.args_uses(uses),
+            TypeVar::Expr(expr) => expr.uses(uses),
             _ => false,
         }
     }
+    /// All function calls made to get the value
+    pub fn calls(&self) -> Vec<&Func> {
+        match self {
+            TypeVar::FunctionCall(call) => {
+                let mut calls = vec![call];
+                calls.append(&mut call.arg_calls());
+                calls
+            }
+            TypeVar::Arr { values } => values.iter().flat_map(|value| value.calls()).collect(),
+            TypeVar::Expr(expr) => expr.calls(),
+            _ => Vec::new(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Variable {
-    pub name: String,
+    pub name: Symbol,
     pub type_: TypeVar,
     pub line: usize,
+    pub span: Span,
+    /// The type written after the name: let count: number = 0;
+    pub annotation: Option<TypesArg>,
+    /// The /// doc comments written above a constant, one entry per line
+    pub docs: Vec<String>,
 }
 
 pub trait VarBuilder {
@@ -65,14 +167,23 @@ pub trait VarBuilder {
     fn type_(&mut self, type_: TypeVar) -> Result<(), ErrorBuilder>;
     /// Assign line to variable
     fn line(&mut self, line: usize) -> &mut Self;
+    /// Assign the source span of the variable
+    fn span(&mut self, span: Span) -> &mut Self;
+    /// Assign the doc comments of the variable
+    fn docs(&mut self, docs: Vec<String>) -> &mut Self;
+    /// Assign the type written after the name of the variable
+    fn annotation(&mut self, annotation: TypesArg) -> &mut Self;
 }
 
 impl VarBuilder for Variable {
     fn new() -> Self {
         Self {
-            name: String::new(),
+            name: Symbol::EMPTY,
             type_: TypeVar::None,
             line: 0,
+            span: Span::default(),
+            annotation: None,
+            docs: Vec::new(),
         }
     }
     fn type_(&mut self, type_: TypeVar) -> Result<(), ErrorBuilder> {
@@ -82,8 +193,7 @@ impl VarBuilder for Variable {
                     "Tried assigning a value to and already variable value: {:?}",
                     type_
                 ))
-                .file_name("should_ad_this:)")
-                .line(self.line)
+                .span(self.span)
                 .build_error());
         }
         self.type_ = type_;
@@ -96,15 +206,116 @@ impl VarBuilder for Variable {
                     "Tried assigning a name to and already named variable: {}",
                     name.as_ref()
                 ))
-                .file_name("should_ad_this:)")
-                .line(self.line)
+                .span(self.span)
                 .build_error());
         }
-        self.name = name.as_ref().to_string();
+        self.name = Symbol::intern(name.as_ref());
         Ok(())
     }
     fn line(&mut self, line: usize) -> &mut Self {
         self.line = line;
         self
     }
+    fn span(&mut self, span: Span) -> &mut Self {
+        self.span = span;
+        self
+    }
+    fn docs(&mut self, docs: Vec<String>) -> &mut Self {
+        self.docs = docs;
+        self
+    }
+    fn annotation(&mut self, annotation: TypesArg) -> &mut Self {
+        self.annotation = Some(annotation);
+        self
+    }
+}
+
+/// The place a assignment stores its value in
+#[derive(Debug, PartialEq, Clone)]
+pub enum AssignTarget {
+    /// a = 1;
+    Variable(Symbol),
+    /// arr[i] = 1; grid[i][j] = 1; p.arr[i] = 1;
+    ///
+    /// The array can be any identifier, field access or index, like the value of a field.
+    Index { array: Expr, index: Expr },
+    /// p.x = 1; p.pos.x = 1; points[0].x = 1;
+    ///
+    /// The value is the struct the field belongs to, it can be any identifier, field access or
+    /// index.
+    Field { value: Expr, field: Symbol },
+}
+
+impl AssignTarget {
+    /// The name of the variable that gets changed
+    pub fn name(&self) -> Symbol {
+        match self {
+            AssignTarget::Variable(name) => *name,
+            AssignTarget::Index { array: place, .. } | AssignTarget::Field { value: place, .. } => {
+                place.root().unwrap_or(Symbol::EMPTY)
+            }
+        }
+    }
+}
+
+/// Checks if the indexes used to find a place use a certain identifier, the variable the place
+/// starts from isn't read.
+fn place_uses(place: &Expr, value: Symbol) -> bool {
+    match &place.kind {
+        ExprKind::Index { array, index } => place_uses(array, value) || index.uses(value),
+        ExprKind::Field { value: place, .. } => place_uses(place, value),
+        _ => false,
+    }
+}
+
+/// Stores a new value in a variable that already exists
+///
+/// a = a + 1;
+///
+/// a += 1; -> op is Some(BinOp::Add)
+#[derive(Debug, PartialEq, Clone)]
+pub struct Assign {
+    pub target: AssignTarget,
+    /// The operator of a compound assignment such as +=, None for =
+    pub op: Option<BinOp>,
+    pub value: Expr,
+    pub span: Span,
+}
+
+impl Assign {
+    pub fn new(target: AssignTarget, op: Option<BinOp>, value: Expr, span: Span) -> Assign {
+        Assign {
+            target,
+            op,
+            value,
+            span,
+        }
+    }
+
+    /// Checks if the assignment reads a certain identifier
+    ///
+    /// Only a compound assignment reads the variable it changes, a = 1 doesn't use a.
+    pub fn uses(&self, value: Symbol) -> bool {
+        let index_uses = match &self.target {
+            AssignTarget::Index { array, index } => place_uses(array, value) || index.uses(value),
+            AssignTarget::Field { value: place, .. } => place_uses(place, value),
+            AssignTarget::Variable(_) => false,
+        };
+        let reads_target = self.op.is_some() && self.target.name() == value;
+        self.value.uses(value) || index_uses || reads_target
+    }
+
+    /// All function calls made within the assignment
+    pub fn calls(&self) -> Vec<&Func> {
+        let mut calls = self.value.calls();
+        match &self.target {
+            AssignTarget::Index { array, index } => {
+                calls.append(&mut array.calls());
+                calls.append(&mut index.calls());
+            }
+            AssignTarget::Field { value, .. } => calls.append(&mut value.calls()),
+            AssignTarget::Variable(_) => {}
+        }
+        calls
+    }
 }
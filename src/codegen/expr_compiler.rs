@@ -0,0 +1,578 @@
+use inkwell::{
+    module::Linkage,
+    types::{BasicType, BasicTypeEnum, IntType},
+    values::{
+        AnyValueEnum, BasicMetadataValueEnum, BasicValueEnum, FunctionValue, IntValue,
+        PointerValue,
+    },
+    IntPredicate,
+};
+
+use super::{
+    get_args_function::{get_int_value, Args},
+    mangle::mangle,
+    struct_compiler::GenStruct,
+    CodeGen,
+};
+use crate::{
+    ast::{
+        ast::Arg,
+        expr::{BinOp, Expr, ExprKind, UnaryOp},
+        function::Func,
+    },
+    parser_lexer::{lexer::Span, symbol::Symbol},
+};
+
+pub trait GenExpr<'ctx> {
+    /// Generates the instructions calculating the expression and returns the resulting value
+    ///
+    /// Numbers are i32, chars i8 and the result of comparisons and && || are i1.
+    fn gen_expr(
+        &self,
+        expr: &Expr,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, String>;
+    /// Generates the expression and turns it into a boolean, anything that isn't zero is true
+    fn gen_bool(
+        &self,
+        expr: &Expr,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<IntValue<'ctx>, String>;
+    /// Applies a binary operator to two values that are already generated
+    ///
+    /// A char or boolean used together with a number is extended to a number first.
+    fn gen_operation(&self, op: BinOp, lhs: IntValue<'ctx>, rhs: IntValue<'ctx>) -> IntValue<'ctx>;
+    /// Stores the values of a array within a local and returns the pointer to it
+    ///
+    /// Every value needs to be of the same type, a empty array is a array of numbers.
+    fn gen_array(
+        &self,
+        values: &[Expr],
+        span: Span,
+        name: &str,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<PointerValue<'ctx>, String>;
+    /// Returns the pointer to a array and its length, the array is a array literal, the name of
+    /// a local array or a array stored in a field or another array
+    fn gen_array_pointer(
+        &self,
+        array: &Expr,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<(PointerValue<'ctx>, u32), String>;
+    /// Returns the pointer to the value at the index of the array
+    ///
+    /// With bounds checks enabled the program stops with the line of the index when the index is
+    /// outside of the array.
+    fn gen_element_pointer(
+        &self,
+        array: (PointerValue<'ctx>, u32),
+        index: &Expr,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<PointerValue<'ctx>, String>;
+    /// Returns the pointer to the place the value of the expression is stored in
+    ///
+    /// Locals, fields and the values of a array are changed through the pointer, any other value
+    /// is stored in a new local first.
+    fn gen_place(
+        &self,
+        expr: &Expr,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<PointerValue<'ctx>, String>;
+    /// Turns the value into the given type, None if the types are different
+    ///
+    /// Numbers, chars and booleans are converted into each other, a boolean is zero extended so
+    /// true stays 1.
+    fn gen_cast(
+        &self,
+        value: BasicValueEnum<'ctx>,
+        type_: BasicTypeEnum<'ctx>,
+    ) -> Option<BasicValueEnum<'ctx>>;
+}
+
+trait GenExprKinds<'ctx> {
+    fn gen_expr_id(
+        &self,
+        id: Symbol,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, String>;
+    fn gen_expr_call(
+        &self,
+        call: &Func,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, String>;
+    /// Std functions that result in a value, so they can be used within expressions
+    fn gen_std_call(
+        &self,
+        call: &Func,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, String>;
+    /// Stops the program when the index isn't within the array
+    fn gen_bounds_check(
+        &self,
+        index: IntValue<'ctx>,
+        len: u32,
+        span: Span,
+        func: &FunctionValue<'ctx>,
+    );
+    fn gen_binary(
+        &self,
+        op: BinOp,
+        lhs: IntValue<'ctx>,
+        rhs: IntValue<'ctx>,
+    ) -> IntValue<'ctx>;
+    fn gen_unary(&self, op: UnaryOp, value: IntValue<'ctx>) -> IntValue<'ctx>;
+    /// Generates && and ||, the right side is only evaluated when the left side doesn't decide
+    /// the result, like the conditions of if statements and loops
+    fn gen_short_circuit(
+        &self,
+        op: BinOp,
+        lhs: &Expr,
+        rhs: &Expr,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<IntValue<'ctx>, String>;
+    /// Widens the value to the given type, a boolean is zero extended so true stays 1
+    fn gen_extend(&self, value: IntValue<'ctx>, type_: IntType<'ctx>) -> IntValue<'ctx>;
+    fn gen_int(
+        &self,
+        expr: &Expr,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<IntValue<'ctx>, String>;
+    fn to_bool(&self, value: IntValue<'ctx>) -> IntValue<'ctx>;
+}
+
+impl<'ctx> GenExprKinds<'ctx> for CodeGen<'ctx> {
+    fn gen_expr_id(
+        &self,
+        id: Symbol,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        if let Some(local) = self.get_local(id) {
+            match local {
+                AnyValueEnum::PointerValue(pointer) => {
+                    return Ok(self.builder.build_load(pointer, &mangle(id)));
+                }
+                AnyValueEnum::IntValue(int) => return Ok(int.into()),
+                _ => (),
+            }
+        }
+
+        if let Some(param) = function
+            .get_arg_index_(id)
+            .and_then(|index| func.get_nth_param(index))
+        {
+            return Ok(param);
+        }
+
+        if let Some(global) = self.module.get_global(&mangle(id)) {
+            return Ok(self.builder.build_load(global.as_pointer_value(), &mangle(id)));
+        }
+
+        Err(format!("{id} doesn't exist within this scope."))
+    }
+    fn gen_expr_call(
+        &self,
+        call: &Func,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let Some(call_fn) = self.module.get_function(&mangle(call.name)) else {
+            return self.gen_std_call(call, function, func);
+        };
+        let args = self.get_args_value(call, function, func)?;
+        self.builder
+            .build_call(call_fn, &args, "call")
+            .try_as_basic_value()
+            .left()
+            .ok_or_else(|| format!("The function {} doesn't return a value", call.name))
+    }
+    fn gen_std_call(
+        &self,
+        call: &Func,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        match call.name.as_str() {
+            // @add(a, b) is the same as a + b
+            "@add" => match self.get_args_value(call, function, func)?.as_slice() {
+                [BasicMetadataValueEnum::IntValue(lhs), BasicMetadataValueEnum::IntValue(rhs)] => {
+                    Ok(self.gen_operation(BinOp::Add, *lhs, *rhs).into())
+                }
+                args => Err(format!(
+                    "@add expects 2 numbers but got {} arguments, on line {}",
+                    args.len(),
+                    call.line
+                )),
+            },
+            // @len(arr) is the amount of values within the array, it is known while compiling
+            "@len" => {
+                let [Arg {
+                    expr: Some(array), ..
+                }] = call.args.as_slice()
+                else {
+                    return Err(format!(
+                        "@len expects 1 array but got {} arguments, on line {}",
+                        call.args.len(),
+                        call.line
+                    ));
+                };
+                let (_, len) = self.gen_array_pointer(array, function, func)?;
+                Ok(self.context.i32_type().const_int(len as u64, false).into())
+            }
+            _ => Err(format!("Call to function that doesn't exist {}", call.name)),
+        }
+    }
+    fn gen_bounds_check(
+        &self,
+        index: IntValue<'ctx>,
+        len: u32,
+        span: Span,
+        func: &FunctionValue<'ctx>,
+    ) {
+        let i32_type = self.context.i32_type();
+        let len = i32_type.const_int(len as u64, false);
+        // A negative index is a huge unsigned number, so one comparison checks both ends
+        let in_bounds = self
+            .builder
+            .build_int_compare(IntPredicate::ULT, index, len, "in_bounds");
+        let fail_block = self.context.append_basic_block(*func, "out_of_bounds");
+        let ok_block = self.context.append_basic_block(*func, "in_bounds");
+        self.builder
+            .build_conditional_branch(in_bounds, ok_block, fail_block);
+
+        self.builder.position_at_end(fail_block);
+        let message = self.builder.build_global_string_ptr(
+            &format!("Index out of bounds on line {}\n", span.line),
+            "bounds_error",
+        );
+        self.builder.build_call(
+            self.get_printf(),
+            &[message.as_pointer_value().into()],
+            "printf_call",
+        );
+        let exit = self.module.get_function("exit").unwrap_or_else(|| {
+            let exit = self.context.void_type().fn_type(&[i32_type.into()], false);
+            self.module.add_function("exit", exit, Some(Linkage::External))
+        });
+        self.builder
+            .build_call(exit, &[i32_type.const_int(1, false).into()], "exit_call");
+        self.builder.build_unreachable();
+
+        self.builder.position_at_end(ok_block);
+    }
+    fn gen_binary(
+        &self,
+        op: BinOp,
+        lhs: IntValue<'ctx>,
+        rhs: IntValue<'ctx>,
+    ) -> IntValue<'ctx> {
+        let builder = &self.builder;
+        match op {
+            BinOp::Add => builder.build_int_add(lhs, rhs, "add"),
+            BinOp::Sub => builder.build_int_sub(lhs, rhs, "sub"),
+            BinOp::Mul => builder.build_int_mul(lhs, rhs, "mul"),
+            BinOp::Div => builder.build_int_signed_div(lhs, rhs, "div"),
+            BinOp::Rem => builder.build_int_signed_rem(lhs, rhs, "rem"),
+            BinOp::EqEq => builder.build_int_compare(IntPredicate::EQ, lhs, rhs, "eq"),
+            BinOp::NotEq => builder.build_int_compare(IntPredicate::NE, lhs, rhs, "not_eq"),
+            BinOp::Less => builder.build_int_compare(IntPredicate::SLT, lhs, rhs, "less"),
+            BinOp::LessEq => builder.build_int_compare(IntPredicate::SLE, lhs, rhs, "less_eq"),
+            BinOp::More => builder.build_int_compare(IntPredicate::SGT, lhs, rhs, "more"),
+            BinOp::MoreEq => builder.build_int_compare(IntPredicate::SGE, lhs, rhs, "more_eq"),
+            BinOp::And => builder.build_and(self.to_bool(lhs), self.to_bool(rhs), "and"),
+            BinOp::Or => builder.build_or(self.to_bool(lhs), self.to_bool(rhs), "or"),
+        }
+    }
+    fn gen_unary(&self, op: UnaryOp, value: IntValue<'ctx>) -> IntValue<'ctx> {
+        match op {
+            UnaryOp::Neg => self.builder.build_int_neg(value, "neg"),
+            UnaryOp::Not => self.builder.build_not(self.to_bool(value), "not"),
+        }
+    }
+    fn gen_short_circuit(
+        &self,
+        op: BinOp,
+        lhs: &Expr,
+        rhs: &Expr,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<IntValue<'ctx>, String> {
+        let line = lhs.span.line;
+        let no_block = || format!("Found a expression outside of a function, on line {line}");
+        let lhs = self.gen_bool(lhs, function, func)?;
+        let lhs_block = self.builder.get_insert_block().ok_or_else(no_block)?;
+        let rhs_block = self.context.append_basic_block(*func, "rhs");
+        let end_block = self.context.append_basic_block(*func, "end_logic");
+        // When the left side already decides the result it is the result
+        match op {
+            BinOp::And => self
+                .builder
+                .build_conditional_branch(lhs, rhs_block, end_block),
+            _ => self
+                .builder
+                .build_conditional_branch(lhs, end_block, rhs_block),
+        };
+
+        self.builder.position_at_end(rhs_block);
+        let rhs = self.gen_bool(rhs, function, func)?;
+        // The right side can contain a && or || of its own, which moves the builder
+        let rhs_block = self.builder.get_insert_block().ok_or_else(no_block)?;
+        self.builder.build_unconditional_branch(end_block);
+
+        self.builder.position_at_end(end_block);
+        let result = self.builder.build_phi(self.context.bool_type(), "logic");
+        result.add_incoming(&[(&lhs, lhs_block), (&rhs, rhs_block)]);
+        Ok(result.as_basic_value().into_int_value())
+    }
+    fn gen_extend(&self, value: IntValue<'ctx>, type_: IntType<'ctx>) -> IntValue<'ctx> {
+        if value.get_type().get_bit_width() == 1 {
+            return self.builder.build_int_z_extend(value, type_, "extend");
+        }
+        self.builder.build_int_s_extend(value, type_, "extend")
+    }
+    fn gen_int(
+        &self,
+        expr: &Expr,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<IntValue<'ctx>, String> {
+        let value = self.gen_expr(expr, function, func)?;
+        get_int_value(value).ok_or_else(|| {
+            format!(
+                "Only numbers, chars and booleans can be used in calculations, on line {}",
+                expr.span.line
+            )
+        })
+    }
+    fn to_bool(&self, value: IntValue<'ctx>) -> IntValue<'ctx> {
+        if value.get_type().get_bit_width() == 1 {
+            return value;
+        }
+        let zero = value.get_type().const_int(0, false);
+        self.builder
+            .build_int_compare(IntPredicate::NE, value, zero, "to_bool")
+    }
+}
+
+impl<'ctx> GenExpr<'ctx> for CodeGen<'ctx> {
+    fn gen_expr(
+        &self,
+        expr: &Expr,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let value = match &expr.kind {
+            ExprKind::Number(num) => self.context.i32_type().const_int(*num as u64, false),
+            ExprKind::Char(char) => self.context.i8_type().const_int(*char as u64, false),
+            ExprKind::Bool(bool) => self.context.bool_type().const_int(*bool as u64, false),
+            ExprKind::String(string) => {
+                let string = self.builder.build_global_string_ptr(string, "str");
+                return Ok(string.as_pointer_value().into());
+            }
+            ExprKind::Identifier(id) => return self.gen_expr_id(*id, function, func),
+            ExprKind::Call(call) => return self.gen_expr_call(call, function, func),
+            ExprKind::Array(_) => {
+                return Err(format!(
+                    "Arrays can't be used within expressions yet, on line {}",
+                    expr.span.line
+                ))
+            }
+            ExprKind::Index { array, index } => {
+                let array = self.gen_array_pointer(array, function, func)?;
+                let element = self.gen_element_pointer(array, index, function, func)?;
+                return Ok(self.builder.build_load(element, "element"));
+            }
+            ExprKind::StructLit { name, fields } => {
+                return self.gen_struct_literal(*name, fields, expr.span, function, func);
+            }
+            ExprKind::Field { field, .. } => {
+                let pointer = self.gen_place(expr, function, func)?;
+                return Ok(self.builder.build_load(pointer, &mangle(*field)));
+            }
+            ExprKind::Binary {
+                op: op @ (BinOp::And | BinOp::Or),
+                lhs,
+                rhs,
+            } => self.gen_short_circuit(*op, lhs, rhs, function, func)?,
+            ExprKind::Binary { op, lhs, rhs } => {
+                let lhs = self.gen_int(lhs, function, func)?;
+                let rhs = self.gen_int(rhs, function, func)?;
+                self.gen_operation(*op, lhs, rhs)
+            }
+            ExprKind::Unary { op, expr } => {
+                let value = self.gen_int(expr, function, func)?;
+                self.gen_unary(*op, value)
+            }
+        };
+        Ok(value.into())
+    }
+    fn gen_bool(
+        &self,
+        expr: &Expr,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<IntValue<'ctx>, String> {
+        let value = self.gen_int(expr, function, func)?;
+        Ok(self.to_bool(value))
+    }
+    fn gen_operation(
+        &self,
+        op: BinOp,
+        mut lhs: IntValue<'ctx>,
+        mut rhs: IntValue<'ctx>,
+    ) -> IntValue<'ctx> {
+        let (lhs_width, rhs_width) = (
+            lhs.get_type().get_bit_width(),
+            rhs.get_type().get_bit_width(),
+        );
+        if lhs_width < rhs_width {
+            lhs = self.gen_extend(lhs, rhs.get_type());
+        } else if rhs_width < lhs_width {
+            rhs = self.gen_extend(rhs, lhs.get_type());
+        }
+        self.gen_binary(op, lhs, rhs)
+    }
+    fn gen_array(
+        &self,
+        values: &[Expr],
+        span: Span,
+        name: &str,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<PointerValue<'ctx>, String> {
+        let values = values
+            .iter()
+            .map(|value| self.gen_expr(value, function, func))
+            .collect::<Result<Vec<_>, _>>()?;
+        let type_ = values
+            .first()
+            .map_or(self.context.i32_type().into(), |value| value.get_type());
+        if values.iter().any(|value| value.get_type() != type_) {
+            return Err(format!(
+                "All values within a array need to be of the same type, on line {}",
+                span.line
+            ));
+        }
+
+        let i32_type = self.context.i32_type();
+        let array = self.build_entry_alloca(type_.array_type(values.len() as u32), name);
+        for (index, value) in values.into_iter().enumerate() {
+            let indexes = [i32_type.const_zero(), i32_type.const_int(index as u64, false)];
+            // The index is always within the array we just allocated
+            let element = unsafe { self.builder.build_in_bounds_gep(array, &indexes, "element") };
+            self.builder.build_store(element, value);
+        }
+        Ok(array)
+    }
+    fn gen_array_pointer(
+        &self,
+        array: &Expr,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<(PointerValue<'ctx>, u32), String> {
+        let pointer = match &array.kind {
+            ExprKind::Array(values) => self.gen_array(values, array.span, "array", function, func)?,
+            ExprKind::Identifier(id) => match self.get_local(*id) {
+                Some(AnyValueEnum::PointerValue(pointer)) => pointer,
+                _ => return Err(format!("{id} isn't a array, on line {}", array.span.line)),
+            },
+            // A array within a struct or another array: p.arr grid[i]
+            _ => self.gen_place(array, function, func)?,
+        };
+
+        let type_ = pointer.get_type().get_element_type();
+        if !type_.is_array_type() {
+            return Err(format!(
+                "Expected a array but found a value of a different type, on line {}",
+                array.span.line
+            ));
+        }
+        Ok((pointer, type_.into_array_type().len()))
+    }
+    fn gen_element_pointer(
+        &self,
+        (array, len): (PointerValue<'ctx>, u32),
+        index: &Expr,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<PointerValue<'ctx>, String> {
+        let value = self.gen_expr(index, function, func)?;
+        let Some(value) = get_int_value(value) else {
+            return Err(format!(
+                "A array can only be indexed by a number, on line {}",
+                index.span.line
+            ));
+        };
+        let i32_type = self.context.i32_type();
+        // Booleans and chars are zero extended, sign extending would turn true into -1
+        let value = if value.get_type().get_bit_width() < i32_type.get_bit_width() {
+            self.builder.build_int_z_extend(value, i32_type, "index")
+        } else {
+            self.builder.build_int_cast(value, i32_type, "index")
+        };
+        if self.bounds_checks {
+            self.gen_bounds_check(value, len, index.span, func);
+        }
+
+        let indexes = [i32_type.const_zero(), value];
+        // Without bounds checks a index outside of the array is undefined behaviour, like in C
+        Ok(unsafe { self.builder.build_in_bounds_gep(array, &indexes, "element") })
+    }
+    fn gen_place(
+        &self,
+        expr: &Expr,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<PointerValue<'ctx>, String> {
+        match &expr.kind {
+            ExprKind::Identifier(id) => {
+                if let Some(AnyValueEnum::PointerValue(local)) = self.get_local(*id) {
+                    return Ok(local);
+                }
+            }
+            ExprKind::Field { value, field } => {
+                let value = self.gen_place(value, function, func)?;
+                return self.gen_field_pointer(value, *field, expr.span);
+            }
+            ExprKind::Index { array, index } => {
+                let array = self.gen_array_pointer(array, function, func)?;
+                return self.gen_element_pointer(array, index, function, func);
+            }
+            _ => (),
+        }
+        // The value of a call or literal only exists while the expression is generated
+        let value = self.gen_expr(expr, function, func)?;
+        let place = self.build_entry_alloca(value.get_type(), "place");
+        self.builder.build_store(place, value);
+        Ok(place)
+    }
+    fn gen_cast(
+        &self,
+        value: BasicValueEnum<'ctx>,
+        type_: BasicTypeEnum<'ctx>,
+    ) -> Option<BasicValueEnum<'ctx>> {
+        if value.get_type() == type_ {
+            return Some(value);
+        }
+        let (Some(int), BasicTypeEnum::IntType(int_type)) = (get_int_value(value), type_) else {
+            return None;
+        };
+        let value = if int.get_type().get_bit_width() == 1 {
+            self.builder.build_int_z_extend(int, int_type, "cast")
+        } else {
+            self.builder.build_int_cast(int, int_type, "cast")
+        };
+        Some(value.into())
+    }
+}
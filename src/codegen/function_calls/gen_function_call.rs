@@ -1,20 +1,39 @@
 use inkwell::values::FunctionValue;
 
 use crate::codegen::get_args_function::Args;
+use crate::codegen::mangle::mangle;
 use crate::codegen::std_compiler::Std;
 use crate::codegen::CodeGen;
 use crate::codegen::LOGGER;
 use crate::{ast::function::Func, errors::logger::Log};
 
 pub trait GenFunctionCall<'ctx> {
-    fn gen_function_call(&self, function: &Func, func: &FunctionValue<'ctx>) -> Result<(), String>;
+    /// Generates a call that is a statement on its own, function is the function the call is
+    /// made in.
+    fn gen_function_call(
+        &self,
+        call: &Func,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<(), String>;
 }
 
 impl<'ctx> GenFunctionCall<'ctx> for CodeGen<'ctx> {
-    fn gen_function_call(&self, function: &Func, func: &FunctionValue<'ctx>) -> Result<(), String> {
-        let args = &self.get_args_value(function, func);
+    fn gen_function_call(
+        &self,
+        call: &Func,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<(), String> {
+        let args = &match self.get_args_value(call, function, func) {
+            Ok(args) => args,
+            Err(error) => {
+                LOGGER.error(&error);
+                return Err(error);
+            }
+        };
 
-        let std = &self.std_functions(function, args.clone(), None);
+        let std = &self.std_functions(call, args.clone(), None);
         match std {
             Ok(_) => {
                 return Ok(());
@@ -22,8 +41,8 @@ impl<'ctx> GenFunctionCall<'ctx> for CodeGen<'ctx> {
             Err(_) => (),
         }
 
-        let Some(func) = &self.module.get_function(&function.name) else {
-                let error = format!("Call to function that doesn't exist {} on line", &function.name);
+        let Some(func) = &self.module.get_function(&mangle(call.name)) else {
+                let error = format!("Call to function that doesn't exist {} on line", &call.name);
                 LOGGER.error(&error);
                 return Err(error)
         };
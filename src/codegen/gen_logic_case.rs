@@ -1,115 +1,174 @@
-use inkwell::{basic_block::BasicBlock, values::FunctionValue};
+use inkwell::{
+    basic_block::BasicBlock,
+    values::{FunctionValue, IntValue},
+};
 
-use super::{CodeGen, Gen};
-use crate::ast::{
-    ast::{Case, Logic, Type},
-    function::Func,
-    variable::TypeVar,
+use super::{expr_compiler::GenExpr, CodeGen, Gen};
+use crate::{
+    ast::{
+        ast::{Ast, Condition, Logic, Type},
+        function::Func,
+    },
+    parser_lexer::lexer::Span,
 };
 
 pub trait GenLogicCase<'ctx> {
-    // Todo: Add support for comparing variables && function calls
-    // I need to add both support for it in the parser and the compiler
+    /// Generates a if statement
+    ///
+    /// The condition jumps to the then block or the else block, both of them continue in the
+    /// block after the if statement.
     fn gen_logic_case(
         &self,
         function: &Func,
         logic: &Logic,
-        block: &BasicBlock,
         func: &FunctionValue<'ctx>,
     ) -> Result<(), String>;
 }
 
-impl<'ctx> GenLogicCase<'ctx> for CodeGen<'ctx> {
-    // Todo: Updated it for multiple cases so this currently doesn't work
-    fn gen_logic_case(
+pub trait GenCondition<'ctx> {
+    /// Jumps to then_block if the condition is true and to else_block if it isn't
+    ///
+    /// The right side of && and || is only evaluated when it can still change the result:
+    ///
+    /// a && b -> if a is false we jump to else_block without checking b
+    ///
+    /// a || b -> if a is true we jump to then_block without checking b
+    fn gen_condition(
         &self,
+        condition: &Condition,
+        span: Span,
         function: &Func,
-        logic: &Logic,
-        block: &BasicBlock,
         func: &FunctionValue<'ctx>,
+        branches: (BasicBlock<'ctx>, BasicBlock<'ctx>),
+    ) -> Result<(), String>;
+    fn gen_condition_value(
+        &self,
+        condition: &Condition,
+        span: Span,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<IntValue<'ctx>, String>;
+    fn gen_branch_body(
+        &self,
+        function: &Func,
+        body: &Ast,
+        func: &FunctionValue<'ctx>,
+        block: BasicBlock<'ctx>,
+        end_block: BasicBlock<'ctx>,
+    ) -> Result<(), String>;
+}
+
+impl<'ctx> GenCondition<'ctx> for CodeGen<'ctx> {
+    fn gen_condition(
+        &self,
+        condition: &Condition,
+        span: Span,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+        (then_block, else_block): (BasicBlock<'ctx>, BasicBlock<'ctx>),
     ) -> Result<(), String> {
-        match &logic.if_.get(0).unwrap() {
-            Case::None => {
-                return Err("Found if without any comparisons!".into());
+        match condition {
+            Condition::And(one, two) => {
+                let rhs_block = self.context.append_basic_block(*func, "and");
+                self.gen_condition(one, span, function, func, (rhs_block, else_block))?;
+                self.builder.position_at_end(rhs_block);
+                self.gen_condition(two, span, function, func, (then_block, else_block))
             }
-            Case::EqEq(_val1, _val2) => {}
-            Case::More(val1, val2) => {
-                compile_compare_nums(self, function, logic, block, func, "more", (val1, val2))?;
+            Condition::Or(one, two) => {
+                let rhs_block = self.context.append_basic_block(*func, "or");
+                self.gen_condition(one, span, function, func, (then_block, rhs_block))?;
+                self.builder.position_at_end(rhs_block);
+                self.gen_condition(two, span, function, func, (then_block, else_block))
             }
-            Case::MoreEq(val1, val2) => {
-                compile_compare_nums(self, function, logic, block, func, "more_eq", (val1, val2))?;
+            Condition::Not(condition) => {
+                self.gen_condition(condition, span, function, func, (else_block, then_block))
             }
-            Case::Less(val1, val2) => {
-                compile_compare_nums(self, function, logic, block, func, "less", (val1, val2))?;
+            condition => {
+                let value = self.gen_condition_value(condition, span, function, func)?;
+                self.builder
+                    .build_conditional_branch(value, then_block, else_block);
+                Ok(())
             }
-            Case::LessEq(val1, val2) => {
-                compile_compare_nums(self, function, logic, block, func, "less_eq", (val1, val2))?;
+        }
+    }
+    fn gen_condition_value(
+        &self,
+        condition: &Condition,
+        span: Span,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<IntValue<'ctx>, String> {
+        let expr = match condition {
+            Condition::Case(case) => case.to_expr(span),
+            Condition::Value(value) => value.to_expr(span),
+            _ => None,
+        };
+        let Some(expr) = expr else {
+            return Err(format!(
+                "Found if without any comparisons! on line {}",
+                span.line
+            ));
+        };
+        self.gen_bool(&expr, function, func)
+    }
+    fn gen_branch_body(
+        &self,
+        function: &Func,
+        body: &Ast,
+        func: &FunctionValue<'ctx>,
+        block: BasicBlock<'ctx>,
+        end_block: BasicBlock<'ctx>,
+    ) -> Result<(), String> {
+        self.builder.position_at_end(block);
+        match &body.type_ {
+            Type::Block => self.gen_block_func(function, body.body.clone(), func, &block),
+            // else if, it gets its own blocks and continues in our end block once it's done
+            Type::Logic(logic) => self.gen_logic_case(function, logic, func)?,
+            t => {
+                return Err(format!(
+                    "Found invalid token type after comparison, {:#?}",
+                    t
+                ))
             }
         }
+        // A body that returns already ended its block
+        let current = self.builder.get_insert_block();
+        if current.is_some_and(|current| current.get_terminator().is_none()) {
+            self.builder.build_unconditional_branch(end_block);
+        }
         Ok(())
     }
 }
 
-pub fn compare_nums(num_1: i32, num_2: i32, case: &str) -> bool {
-    match case {
-        "more" => num_1 > num_2,
-        "less" => num_1 < num_2,
-        "more_eq" => num_1 >= num_2,
-        "less_eq" => num_1 <= num_2,
-        _ => false,
-    }
-}
+impl<'ctx> GenLogicCase<'ctx> for CodeGen<'ctx> {
+    fn gen_logic_case(
+        &self,
+        function: &Func,
+        logic: &Logic,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<(), String> {
+        let then_block = self.context.append_basic_block(*func, "then");
+        let else_block = self.context.append_basic_block(*func, "else");
+        let end_block = self.context.append_basic_block(*func, "end_if");
 
-pub fn compile_compare_nums<'ctx>(
-    code: &CodeGen<'ctx>,
-    function: &Func,
-    logic: &Logic,
-    block: &BasicBlock,
-    func: &FunctionValue<'ctx>,
-    case: &str,
-    (val1, val2): (&TypeVar, &TypeVar),
-) -> Result<(), String> {
-    match (val1, val2) {
-        // For static values we just replace the code with what it would do if comparison is true or false
-        (TypeVar::Number(num1), TypeVar::Number(num2)) => {
-            if compare_nums(*num1, *num2, case) {
-                match &logic.do_.type_ {
-                    Type::Block => {
-                        code.gen_block_func(function, logic.do_.body.clone(), func, block);
-                    }
-                    t => {
-                        return Err(format!(
-                            "Found invalid token type after comparison, {:#?}",
-                            t
-                        ))
-                    }
-                }
-            } else if logic.else_.is_some() {
-                let else_ = logic.else_.clone();
-                if else_.is_some() {
-                    let else_ = else_.unwrap();
-                    match else_.type_ {
-                        Type::Block => {
-                            code.gen_block_func(function, else_.body, func, block);
-                        }
-                        t => {
-                            return Err(format!(
-                                "Found invalid token type after comparison, {:#?}",
-                                t
-                            ))
-                        }
-                    }
-                }
+        self.gen_condition(
+            &logic.if_,
+            logic.span,
+            function,
+            func,
+            (then_block, else_block),
+        )?;
+
+        self.gen_branch_body(function, &logic.do_, func, then_block, end_block)?;
+        match &logic.else_ {
+            Some(else_) => self.gen_branch_body(function, else_, func, else_block, end_block)?,
+            None => {
+                self.builder.position_at_end(else_block);
+                self.builder.build_unconditional_branch(end_block);
             }
         }
-        // Todo:  Handle identifiers for if statements
-        (TypeVar::Identifier(_), TypeVar::Identifier(_)) => {}
-        case => {
-            return Err(format!(
-                "Cannot check if {:#?} is more then {:#?}",
-                case.0, case.1
-            ))
-        }
+
+        self.builder.position_at_end(end_block);
+        Ok(())
     }
-    Ok(())
 }
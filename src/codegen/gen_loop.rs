@@ -0,0 +1,214 @@
+use inkwell::{
+    basic_block::BasicBlock,
+    values::{FunctionValue, IntValue},
+    IntPredicate,
+};
+
+use super::{
+    expr_compiler::GenExpr, gen_logic_case::GenCondition, get_args_function::get_int_value,
+    mangle::mangle, CodeGen, Gen,
+};
+use crate::{
+    ast::{
+        ast::{For, ForIter, While},
+        expr::Expr,
+        function::Func,
+    },
+    parser_lexer::lexer::Span,
+};
+
+/// The blocks a break or continue within a loop jumps to
+#[derive(Debug, Clone, Copy)]
+pub struct LoopBlocks<'ctx> {
+    pub continue_block: BasicBlock<'ctx>,
+    pub break_block: BasicBlock<'ctx>,
+}
+
+pub trait GenLoop<'ctx> {
+    /// Generates a while loop
+    ///
+    /// The header checks the condition and either jumps to the body or the exit block, the body
+    /// jumps back to the header once it's done.
+    fn gen_while(
+        &self,
+        function: &Func,
+        while_: &While,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<(), String>;
+    /// Generates a for loop over a range or a array
+    ///
+    /// The index is kept in a local, the header compares it against the end of the range or the
+    /// length of the array. A continue jumps to the step block so the index is still incremented.
+    fn gen_for(
+        &self,
+        function: &Func,
+        for_: &For,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<(), String>;
+    /// Jumps to the exit of the loop we are currently in
+    fn gen_break(&self, span: Span) -> Result<(), String>;
+    /// Jumps to the start of the next iteration of the loop we are currently in
+    fn gen_continue(&self, span: Span) -> Result<(), String>;
+}
+
+trait GenForIter<'ctx> {
+    fn gen_range_bound(
+        &self,
+        bound: &Expr,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<IntValue<'ctx>, String>;
+}
+
+impl<'ctx> GenForIter<'ctx> for CodeGen<'ctx> {
+    fn gen_range_bound(
+        &self,
+        bound: &Expr,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<IntValue<'ctx>, String> {
+        let value = self.gen_expr(bound, function, func)?;
+        match get_int_value(value) {
+            Some(int) if int.get_type() == self.context.i32_type() => Ok(int),
+            _ => Err(format!(
+                "A range can only contain numbers, on line {}",
+                bound.span.line
+            )),
+        }
+    }
+}
+
+impl<'ctx> GenLoop<'ctx> for CodeGen<'ctx> {
+    fn gen_while(
+        &self,
+        function: &Func,
+        while_: &While,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<(), String> {
+        let header = self.context.append_basic_block(*func, "while_header");
+        let body = self.context.append_basic_block(*func, "while_body");
+        let exit = self.context.append_basic_block(*func, "while_exit");
+
+        self.builder.build_unconditional_branch(header);
+        self.builder.position_at_end(header);
+        self.gen_condition(&while_.condition, while_.span, function, func, (body, exit))?;
+
+        self.builder.position_at_end(body);
+        self.loops.borrow_mut().push(LoopBlocks {
+            continue_block: header,
+            break_block: exit,
+        });
+        self.gen_block_func(function, while_.body.body.clone(), func, &body);
+        self.loops.borrow_mut().pop();
+
+        // The body might have ended with a return, break or continue already
+        let current = self.builder.get_insert_block();
+        if current.is_some_and(|current| current.get_terminator().is_none()) {
+            self.builder.build_unconditional_branch(header);
+        }
+
+        self.builder.position_at_end(exit);
+        Ok(())
+    }
+    fn gen_for(
+        &self,
+        function: &Func,
+        for_: &For,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<(), String> {
+        let i32_type = self.context.i32_type();
+        let (start, end, array) = match &for_.iter {
+            ForIter::Range { start, end } => {
+                let start = self.gen_range_bound(start, function, func)?;
+                let end = self.gen_range_bound(end, function, func)?;
+                (start, end, None)
+            }
+            ForIter::Array(array) => {
+                let (array, len) = self.gen_array_pointer(array, function, func)?;
+                (
+                    i32_type.const_zero(),
+                    i32_type.const_int(len as u64, false),
+                    Some(array),
+                )
+            }
+        };
+
+        let index = self.build_entry_alloca(i32_type, "for.index");
+        self.builder.build_store(index, start);
+
+        let header = self.context.append_basic_block(*func, "for_header");
+        let body = self.context.append_basic_block(*func, "for_body");
+        let step = self.context.append_basic_block(*func, "for_step");
+        let exit = self.context.append_basic_block(*func, "for_exit");
+
+        self.builder.build_unconditional_branch(header);
+        self.builder.position_at_end(header);
+        let current = self.builder.build_load(index, "index").into_int_value();
+        let in_range = self
+            .builder
+            .build_int_compare(IntPredicate::SLT, current, end, "in_range");
+        self.builder.build_conditional_branch(in_range, body, exit);
+
+        // The loop variable is a copy, changing it doesn't change which iteration comes next
+        self.builder.position_at_end(body);
+        let value = match array {
+            Some(array) => {
+                let indexes = [i32_type.const_zero(), current];
+                // The header made sure the index is within the array
+                let element =
+                    unsafe { self.builder.build_in_bounds_gep(array, &indexes, "element") };
+                self.builder.build_load(element, &mangle(for_.var))
+            }
+            None => current.into(),
+        };
+        // Named so it can't be found by the lookup of the locals once the loop is done
+        let var = self.build_entry_alloca(value.get_type(), &format!("for.{}", mangle(for_.var)));
+        self.builder.build_store(var, value);
+
+        self.scoped.borrow_mut().push((for_.var, var));
+        self.loops.borrow_mut().push(LoopBlocks {
+            continue_block: step,
+            break_block: exit,
+        });
+        self.gen_block_func(function, for_.body.body.clone(), func, &body);
+        self.loops.borrow_mut().pop();
+        self.scoped.borrow_mut().pop();
+
+        let current_block = self.builder.get_insert_block();
+        if current_block.is_some_and(|current| current.get_terminator().is_none()) {
+            self.builder.build_unconditional_branch(step);
+        }
+
+        self.builder.position_at_end(step);
+        let current = self.builder.build_load(index, "index").into_int_value();
+        let next = self
+            .builder
+            .build_int_add(current, i32_type.const_int(1, false), "next");
+        self.builder.build_store(index, next);
+        self.builder.build_unconditional_branch(header);
+
+        self.builder.position_at_end(exit);
+        Ok(())
+    }
+    fn gen_break(&self, span: Span) -> Result<(), String> {
+        let Some(loop_) = self.loops.borrow().last().copied() else {
+            return Err(format!(
+                "Found a break outside of a loop on line {}",
+                span.line
+            ));
+        };
+        self.builder.build_unconditional_branch(loop_.break_block);
+        Ok(())
+    }
+    fn gen_continue(&self, span: Span) -> Result<(), String> {
+        let Some(loop_) = self.loops.borrow().last().copied() else {
+            return Err(format!(
+                "Found a continue outside of a loop on line {}",
+                span.line
+            ));
+        };
+        self.builder
+            .build_unconditional_branch(loop_.continue_block);
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+/// Turns a identifier into a name llvm accepts without quoting.
+///
+/// Identifiers may contain any unicode letter, llvm names however only allow ascii letters,
+/// digits, _ $ . and -. Every char that isn't a ascii letter, digit or _ is written as its
+/// codepoint in hex between two $, identifiers can't contain a $ so two different identifiers
+/// never end up with the same name.
+///
+/// # Example
+/// ```
+/// use lepa::codegen::mangle::mangle;
+///
+/// assert_eq!(mangle("größe"), "gr$f6$$df$e");
+/// assert_eq!(mangle("_main"), "_main");
+/// ```
+pub fn mangle(name: impl AsRef<str>) -> String {
+    let name = name.as_ref();
+    let mut mangled = String::with_capacity(name.len());
+    for char in name.chars() {
+        if char.is_ascii_alphanumeric() || char == '_' {
+            mangled.push(char);
+        } else {
+            mangled.push_str(&format!("${:x}$", char as u32));
+        }
+    }
+    mangled
+}
@@ -1,18 +1,30 @@
+pub mod expr_compiler;
 pub mod function_calls;
 pub mod gen_logic_case;
+pub mod gen_loop;
 pub mod get_args_function;
+pub mod mangle;
 pub mod return_compiler;
 pub mod std_compiler;
+pub mod struct_compiler;
 pub mod validation;
 pub mod variables;
 
-use crate::ast::function::Func;
-use std::{env::current_dir, process::Command};
+use crate::{
+    ast::{function::Func, struct_::Struct},
+    parser_lexer::symbol::Symbol,
+};
+use std::{cell::RefCell, collections::HashMap, env::current_dir, process::Command};
 
 use colored::Colorize;
 use inkwell::{
-    basic_block::BasicBlock, builder::Builder, context::Context, module::Module,
-    values::FunctionValue, AddressSpace,
+    basic_block::BasicBlock,
+    builder::Builder,
+    context::Context,
+    module::{Linkage, Module},
+    types::{BasicType, StructType},
+    values::{AnyValue, AnyValueEnum, FunctionValue, PointerValue},
+    AddressSpace,
 };
 
 pub static LOGGER: Logger = Logger(crate::errors::logger::LogLevels::Info);
@@ -23,41 +35,105 @@ use crate::{
     errors::logger::{Log, Logger},
 };
 
-use self::{gen_logic_case::GenLogicCase, return_compiler::GenReturn, variables::GenVar};
+use self::{
+    gen_logic_case::GenLogicCase,
+    gen_loop::{GenLoop, LoopBlocks},
+    return_compiler::GenReturn,
+    struct_compiler::GenStruct,
+    variables::GenVar,
+};
 
 use function_calls::gen_function_call::GenFunctionCall;
+use self::mangle::mangle;
 
 pub struct CodeGen<'ctx> {
     pub context: &'ctx Context,
     pub module: Module<'ctx>,
     pub builder: Builder<'ctx>,
+    /// The loops surrounding the code that is being generated, the innermost loop is last
+    pub loops: RefCell<Vec<LoopBlocks<'ctx>>>,
+    /// The locals of the function that is being generated, the innermost is last
+    ///
+    /// A variable declared within a block, or the variable of a for loop, is removed again at
+    /// the end of it.
+    pub scoped: RefCell<Vec<(Symbol, PointerValue<'ctx>)>>,
+    /// Indexing a array checks if the index is within the array while the program runs
+    pub bounds_checks: bool,
+    /// The structs declared by the program together with their llvm type
+    pub structs: RefCell<HashMap<Symbol, (Struct, StructType<'ctx>)>>,
+}
+
+impl<'ctx> CodeGen<'ctx> {
+    /// Allocates a local variable at the start of the entry block of the current function.
+    ///
+    /// A local declared within the body of a loop would otherwise be allocated again on every
+    /// iteration.
+    pub fn build_entry_alloca<T: BasicType<'ctx>>(&self, type_: T, name: &str) -> PointerValue<'ctx> {
+        let entry = self
+            .builder
+            .get_insert_block()
+            .and_then(|block| block.get_parent())
+            .and_then(|func| func.get_first_basic_block());
+        let Some(entry) = entry else {
+            return self.builder.build_alloca(type_, name);
+        };
+
+        let builder = self.context.create_builder();
+        match entry.get_first_instruction() {
+            Some(first) => builder.position_before(&first),
+            None => builder.position_at_end(entry),
+        }
+        builder.build_alloca(type_, name)
+    }
+
+    /// Allocates the local of a variable, it can be found by its name until the end of the
+    /// block it is declared in.
+    pub fn build_local<T: BasicType<'ctx>>(&self, type_: T, name: Symbol) -> PointerValue<'ctx> {
+        let local = self.build_entry_alloca(type_, &mangle(name));
+        self.scoped.borrow_mut().push((name, local));
+        local
+    }
+
+    /// Finds a local variable of the function by its name.
+    ///
+    /// The innermost variable is found first, so a variable declared within a block shadows a
+    /// variable with the same name outside of it.
+    pub fn get_local(&self, name: Symbol) -> Option<AnyValueEnum<'ctx>> {
+        let scoped = self.scoped.borrow();
+        scoped
+            .iter()
+            .rev()
+            .find(|(var, _)| *var == name)
+            .map(|(_, pointer)| pointer.as_any_value_enum())
+    }
+
+    /// The printf function of libc, it gets declared the first time it's used.
+    pub fn get_printf(&self) -> FunctionValue<'ctx> {
+        if let Some(printf) = self.module.get_function("printf") {
+            return printf;
+        }
+        let i8_pointer = self.context.i8_type().ptr_type(AddressSpace::default());
+        let printf = self.context.i32_type().fn_type(&[i8_pointer.into()], true);
+        self.module
+            .add_function("printf", printf, Some(Linkage::External))
+    }
 }
 
 pub trait Compile {
     ///  Takes the ast and returns the llvm ir string
     fn compile(&self) -> String;
+    /// Compiles the ast like [`Compile::compile`], but the compiled program checks every index of a
+    /// array and stops with the line of the index when it's outside of the array.
+    fn compile_with_bounds_checks(&self) -> String;
     fn create_binary(file_name: &str);
 }
 
 impl Compile for Ast {
     fn compile(&self) -> String {
-        let context = Context::create();
-        let module = context.create_module("start");
-
-        let code_gen = CodeGen {
-            module,
-            context: &context,
-            builder: context.create_builder(),
-        };
-
-        match &self.type_ {
-            crate::ast::ast::Type::Program => {
-                code_gen.compile_gen(self.clone());
-            }
-            _ => LOGGER.error(&"[COMPILER] Ast didn't start with program as first node."),
-        };
-
-        code_gen.module.to_string()
+        compile_ast(self, false)
+    }
+    fn compile_with_bounds_checks(&self) -> String {
+        compile_ast(self, true)
     }
     fn create_binary(file_name: &str) {
         let mut llvm = Command::new("llvm-as");
@@ -99,6 +175,30 @@ impl Compile for Ast {
         }
     }
 }
+
+fn compile_ast(ast: &Ast, bounds_checks: bool) -> String {
+    let context = Context::create();
+    let module = context.create_module("start");
+
+    let code_gen = CodeGen {
+        module,
+        context: &context,
+        builder: context.create_builder(),
+        loops: RefCell::new(Vec::new()),
+        scoped: RefCell::new(Vec::new()),
+        bounds_checks,
+        structs: RefCell::new(HashMap::new()),
+    };
+
+    match &ast.type_ {
+        crate::ast::ast::Type::Program => {
+            code_gen.compile_gen(ast.clone());
+        }
+        _ => LOGGER.error(&"[COMPILER] Ast didn't start with program as first node."),
+    };
+
+    code_gen.module.to_string()
+}
 trait Gen<'ctx> {
     fn compile_gen(&self, ast: Ast);
     fn gen_var(&self, var: &Variable);
@@ -110,10 +210,19 @@ trait Gen<'ctx> {
         func: &FunctionValue<'ctx>,
         block: &BasicBlock,
     );
+    /// Generates the statements of a block, it stops after a statement that ends the block
+    fn gen_statements(
+        &self,
+        function: &Func,
+        ast: Vec<Ast>,
+        func: &FunctionValue<'ctx>,
+        block: &BasicBlock,
+    );
 }
 
 impl<'ctx> Gen<'ctx> for CodeGen<'ctx> {
     fn compile_gen(&self, ast: Ast) {
+        self.gen_structs(&ast);
         for node in ast.body {
             match node.type_ {
                 crate::ast::ast::Type::ConstVar(var) => {
@@ -121,7 +230,7 @@ impl<'ctx> Gen<'ctx> for CodeGen<'ctx> {
                 }
                 crate::ast::ast::Type::Variable(var) => {
                     LOGGER.display_error(&format!(
-                            "Found let keyword outside of function body, consider changing this to: {} {} {} {:#?}; ignoring variable", "const".yellow().bold(), var.name.blue().bold(), "=", var.type_))
+                            "Found let keyword outside of function body, consider changing this to: {} {} {} {:#?}; ignoring variable", "const".yellow().bold(), var.name.as_str().blue().bold(), "=", var.type_))
                 }
                 crate::ast::ast::Type::Function(func) => {
                     let _ = &self.gen_func(&func);
@@ -138,9 +247,17 @@ impl<'ctx> Gen<'ctx> for CodeGen<'ctx> {
                 let number = num.const_int(*number as u64, false);
                 let var = &self
                     .module
-                    .add_global(num, Some(AddressSpace::from(1_u16)), name);
+                    .add_global(num, Some(AddressSpace::from(1_u16)), &mangle(name));
                 var.set_initializer(&number);
             }
+            crate::ast::variable::TypeVar::Char(char) => {
+                let i8_type = self.context.i8_type();
+                let char = i8_type.const_int(*char as u64, false);
+                let var = &self
+                    .module
+                    .add_global(i8_type, Some(AddressSpace::from(1_u16)), &mangle(name));
+                var.set_initializer(&char);
+            }
             _ => (),
         }
     }
@@ -159,6 +276,21 @@ impl<'ctx> Gen<'ctx> for CodeGen<'ctx> {
                 TypesArg::Number => {
                     args.push(self.context.i32_type().into());
                 }
+                TypesArg::Char => {
+                    args.push(self.context.i8_type().into());
+                }
+                // Structs are passed by value, changing a field doesn't change the struct of the
+                // caller
+                TypesArg::Struct(name) => match self.get_struct(name) {
+                    Some((_, type_)) => args.push(type_.into()),
+                    None => {
+                        LOGGER.error(&format!(
+                            "The argument {} of {} has the type {name}, but {name} isn't a struct.",
+                            arg.value, function.name
+                        ));
+                        return;
+                    }
+                },
                 // Todo: Find a way to make this work in a simple way, problem is that we need to
                 // know the length of the expected string, we can either let the user declare that
                 // or detect it ourselfs, the problem however is that there is no way of us to
@@ -174,14 +306,46 @@ impl<'ctx> Gen<'ctx> for CodeGen<'ctx> {
         }
 
         let fn_type = match function.return_type {
+            ast::ReturnTypes::Struct(name) => match self.get_struct(name) {
+                Some((_, type_)) => type_.fn_type(&args, false),
+                None => {
+                    LOGGER.error(&format!(
+                        "{} returns a {name}, but {name} isn't a struct.",
+                        function.name
+                    ));
+                    return;
+                }
+            },
             ast::ReturnTypes::None => self.context.void_type().fn_type(&args, false),
             ast::ReturnTypes::Number => self.context.i32_type().fn_type(&args, false),
-            _ => todo!("Not supported yet..."),
+            ast::ReturnTypes::Char => self.context.i8_type().fn_type(&args, false),
+            ast::ReturnTypes::String => self
+                .context
+                .i8_type()
+                .ptr_type(AddressSpace::default())
+                .fn_type(&args, false),
         };
 
-        let func = &self.module.add_function(&function.name, fn_type, None);
+        let func = &self.module.add_function(&mangle(function.name), fn_type, None);
         let basic_block = &self.context.append_basic_block(*func, "entry");
         self.builder.position_at_end(*basic_block);
+        // A break within a nested function can't leave the loop the function was declared in,
+        // neither can it use the variables of the function around it
+        let loops = self.loops.take();
+        let scoped = self.scoped.take();
+        // Arguments are stored in locals so they can be assigned to like any other variable
+        let typed_args = vars
+            .iter()
+            .filter(|arg| {
+                matches!(
+                    arg.type_,
+                    TypesArg::Number | TypesArg::Char | TypesArg::Struct(_)
+                )
+            });
+        for (arg, param) in typed_args.zip(func.get_params()) {
+            let local = self.build_local(param.get_type(), arg.value);
+            self.builder.build_store(local, param);
+        }
         match &function.body.clone().unwrap().type_ {
             Type::Block => {
                 let _ = &self.gen_block_func(
@@ -196,6 +360,8 @@ impl<'ctx> Gen<'ctx> for CodeGen<'ctx> {
                 token
             )),
         }
+        self.loops.replace(loops);
+        self.scoped.replace(scoped);
     }
     fn gen_block_func(
         &self,
@@ -203,30 +369,76 @@ impl<'ctx> Gen<'ctx> for CodeGen<'ctx> {
         ast: Vec<Ast>,
         func: &FunctionValue<'ctx>,
         block: &BasicBlock,
+    ) {
+        // Variables declared within the block only exist until the end of it
+        let depth = self.scoped.borrow().len();
+        self.gen_statements(function, ast, func, block);
+        self.scoped.borrow_mut().truncate(depth);
+    }
+    fn gen_statements(
+        &self,
+        function: &Func,
+        ast: Vec<Ast>,
+        func: &FunctionValue<'ctx>,
+        block: &BasicBlock,
     ) {
         for token in &ast {
             match &token.type_ {
                 Type::Return(ret) => {
-                    let ret = self.gen_return(ret.clone(), function, func);
+                    let ret = self.gen_return(ret, function, func);
                     if ret.is_err() {
                         LOGGER.error(&ret.err().unwrap());
                     }
+                    // The block is terminated by the return, anything after it can never run
+                    return;
                 }
                 Type::Variable(var) => {
                     let _ = &self.gen_variable(var, function, func);
                 }
                 Type::Function(func) => {
+                    // If statements and loops move the builder, so we continue in the block we
+                    // were in before generating the nested function
+                    let current = self.builder.get_insert_block().unwrap_or(*block);
                     let _ = &self.gen_func(func);
-                    let _ = &self.builder.position_at_end(*block);
+                    let _ = &self.builder.position_at_end(current);
                 }
                 Type::Logic(case) => {
-                    let logic_case = self.gen_logic_case(function, case, block, func);
+                    let logic_case = self.gen_logic_case(function, case, func);
                     if logic_case.is_err() {
                         LOGGER.error(&logic_case.err().unwrap());
                     }
                 }
                 Type::FunctionCall(call) => {
-                    let _call = self.gen_function_call(call, func);
+                    let _call = self.gen_function_call(call, function, func);
+                }
+                Type::While(while_) => {
+                    let while_ = self.gen_while(function, while_, func);
+                    if while_.is_err() {
+                        LOGGER.error(&while_.err().unwrap());
+                    }
+                }
+                Type::For(for_) => {
+                    let for_ = self.gen_for(function, for_, func);
+                    if for_.is_err() {
+                        LOGGER.error(&for_.err().unwrap());
+                    }
+                }
+                Type::Assign(assign) => {
+                    let assign = self.gen_assign(assign, function, func);
+                    if assign.is_err() {
+                        LOGGER.error(&assign.err().unwrap());
+                    }
+                }
+                Type::Break(span) | Type::Continue(span) => {
+                    let jump = match token.type_ {
+                        Type::Break(_) => self.gen_break(*span),
+                        _ => self.gen_continue(*span),
+                    };
+                    if jump.is_err() {
+                        LOGGER.error(&jump.err().unwrap());
+                    }
+                    // Anything after a break or continue can never run
+                    return;
                 }
                 type_ => LOGGER.error(&format!(
                     "This token type is not yet supported for function bodies: {:#?}",
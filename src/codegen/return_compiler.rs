@@ -1,97 +1,89 @@
-use inkwell::values::{AnyValue, AnyValueEnum, FunctionValue};
+use inkwell::values::{BasicValueEnum, FunctionValue};
 
-use super::CodeGen;
-use crate::ast::ast::{Return, ReturnTypes};
+use super::{expr_compiler::GenExpr, struct_compiler::GenStruct, CodeGen};
+use crate::ast::ast::{Return, ReturnTypes, TypesArg};
+use crate::ast::expr::ExprKind;
 use crate::ast::function::Func;
 
-pub trait GenReturn {
+pub trait GenReturn<'ctx> {
+    /// Returns from the function we are in
+    ///
+    /// The returned value has to match the return type of the function, numbers, chars and
+    /// booleans are converted into each other.
     fn gen_return(
         &self,
-        return_type: Return,
+        return_: &Return,
         function: &Func,
-        func: &FunctionValue,
+        func: &FunctionValue<'ctx>,
     ) -> Result<(), String>;
 }
 
-trait GenReturnTypes {
-    fn gen_number(&self, return_type: Return);
-    fn gen_id(&self, return_type: Return, function: &Func, func: &FunctionValue);
-    fn gen_string(&self, return_type: Return);
+trait GenReturnTypes<'ctx> {
+    /// Turns the value into the return type of the function, None if its type is different
+    fn returned_value(
+        &self,
+        function: &Func,
+        value: BasicValueEnum<'ctx>,
+    ) -> Option<BasicValueEnum<'ctx>>;
 }
 
-impl<'ctx> GenReturnTypes for CodeGen<'ctx> {
-    fn gen_number(&self, return_type: Return) {
-        let num: i32 = return_type.value.parse().unwrap();
-        let var = self.context.i32_type();
-        let num = var.const_int(num as u64, false);
-        self.builder.build_return(Some(&num));
-    }
-    fn gen_id(&self, return_type: Return, function: &Func, func: &FunctionValue) {
-        let val = self.module.get_global(&return_type.value);
-
-        if val.is_some() {
-            let val = val.unwrap();
-            let val = val.as_pointer_value();
-            let load = &self.builder.build_load(val, &function.name);
-            self.builder.build_return(Some(load));
-        }
-
-        let local = func.get_first_basic_block();
-        if let Some(local) = local {
-            let local_item = local.get_instruction_with_name(&return_type.value);
-
-            if let Some(local) = local_item {
-                let val = local.as_any_value_enum();
-                match val {
-                    AnyValueEnum::IntValue(int) => {
-                        self.builder.build_return(Some(&int));
-                        return;
-                    }
-                    AnyValueEnum::PointerValue(val) => {
-                        let load = &self.builder.build_load(val, &return_type.value);
-                        self.builder.build_return(Some(load));
-                        return;
-                    }
-                    _ => return,
-                }
-            }
-        }
-
-        let param_ = function.get_arg_index_(&return_type.value);
-        if param_.is_some() {
-            let val = func.get_nth_param(param_.unwrap());
-            if val.is_some() {
-                self.builder.build_return(Some(&val.unwrap()));
-            }
-        }
-    }
-    fn gen_string(&self, _return_type: Return) {
-        todo!("Generating strings as return types is currently not supported")
+impl<'ctx> GenReturnTypes<'ctx> for CodeGen<'ctx> {
+    fn returned_value(
+        &self,
+        function: &Func,
+        value: BasicValueEnum<'ctx>,
+    ) -> Option<BasicValueEnum<'ctx>> {
+        let type_ = match &function.return_type {
+            ReturnTypes::Number => TypesArg::Number,
+            ReturnTypes::Char => TypesArg::Char,
+            // Strings are returned as a pointer to their chars
+            ReturnTypes::String => TypesArg::String,
+            ReturnTypes::Struct(name) => TypesArg::Struct(*name),
+            ReturnTypes::None => return None,
+        };
+        self.gen_cast(value, self.type_of(&type_)?)
     }
 }
 
-impl<'ctx> GenReturn for CodeGen<'ctx> {
+impl<'ctx> GenReturn<'ctx> for CodeGen<'ctx> {
     fn gen_return(
         &self,
-        return_type: Return,
+        return_: &Return,
         function: &Func,
-        func: &FunctionValue,
+        func: &FunctionValue<'ctx>,
     ) -> Result<(), String> {
-        match return_type.type_ {
-            ReturnTypes::Number => {
-                let _ = &self.gen_number(return_type);
-            }
-            ReturnTypes::Identifier => {
-                let _ = &self.gen_id(return_type, function, func);
-            }
-            ReturnTypes::String => {
-                let _ = &self.gen_string(return_type);
-            }
-            ReturnTypes::None => {
-                self.builder.build_return(None);
+        let Some(expr) = &return_.expr else {
+            if function.return_type != ReturnTypes::None {
+                return Err(format!(
+                    "{} returns a {} but the return on line {} doesn't return anything",
+                    function.name, function.return_type, return_.line
+                ));
             }
+            self.builder.build_return(None);
+            return Ok(());
+        };
+        if function.return_type == ReturnTypes::None {
+            return Err(format!(
+                "{} doesn't have a return type but the return on line {} returns a value",
+                function.name, return_.line
+            ));
         }
 
+        let value = match &expr.kind {
+            // Arrays can't be used within expressions, so the array is stored in a local first
+            ExprKind::Array(values) => {
+                let array = self.gen_array(values, expr.span, "return.array", function, func)?;
+                self.builder.build_load(array, "array")
+            }
+            _ => self.gen_expr(expr, function, func)?,
+        };
+        let Some(value) = self.returned_value(function, value) else {
+            return Err(format!(
+                "{} returns a {} but the value returned on line {} has a different type",
+                function.name, function.return_type, return_.line
+            ));
+        };
+        self.builder.build_return(Some(&value));
         Ok(())
     }
 }
@@ -1,12 +1,7 @@
 //! These are all the standard library implementations for mathemathical functions / calculations
 //!
-//! Altough currently I haven't added suport for directly doing something like:
-//!
-//! let a = b + c;
-//!
-//! This will of course be supported in the future.  
-//!
-//! Currently still have to add the parsing for it so it will take some time to add this.
+//! Calculations such as `let a = b + c;` are expressions now and get compiled by
+//! [`crate::codegen::expr_compiler`], these functions are kept so existing code keeps working.
 //!
 //! Current supported
 //!
@@ -19,7 +14,7 @@ use inkwell::values::{BasicMetadataValueEnum, AnyValue, AnyValueEnum};
 
 use crate::{
     ast::function::Func,
-    codegen::{CodeGen, LOGGER},
+    codegen::{mangle::mangle, CodeGen, LOGGER},
     errors::logger::Log,
 };
 
@@ -63,9 +58,9 @@ impl<'ctx> StdMath for CodeGen<'ctx> {
 
         match (add1_value, add2_value) {
             (AnyValueEnum::IntValue(add1_value), AnyValueEnum::IntValue(add2_value)) => {
-                let add = self.builder.build_int_add(add1_value, add2_value, var_name);
+                let add = self.builder.build_int_add(add1_value, add2_value, &mangle(var_name));
                 let i32_type = self.context.i32_type();
-                let assign = self.builder.build_alloca(i32_type, var_name);
+                let assign = self.build_local(i32_type, var_name.into());
                 self.builder.build_store(assign, add);
             }
             // Todo: We should throw a incorrect type value here. 
@@ -1,4 +1,4 @@
-use inkwell::{module::Linkage, values::BasicMetadataValueEnum, AddressSpace};
+use inkwell::values::BasicMetadataValueEnum;
 
 use crate::{
     ast::{ast::TypesArg, function::Func},
@@ -19,33 +19,9 @@ impl<'ctx> StdPrint for CodeGen<'ctx> {
 
         match &first_arg.type_ {
             TypesArg::String => {
-                let printf = &self.module.get_function("printf");
-
-                if printf.is_some() {
-                    let _ = &self.builder.build_call(
-                        printf.unwrap(),
-                        &args_values.clone(),
-                        "printf_call",
-                    );
-                    return;
-                }
-
-                let print_f = &self.context.i32_type().fn_type(
-                    &[self
-                        .context
-                        .i8_type()
-                        .ptr_type(AddressSpace::default())
-                        .into()],
-                    true,
-                );
-
-                let printf = &self
-                    .module
-                    .add_function("printf", *print_f, Some(Linkage::External));
-
                 let _ = &self
                     .builder
-                    .build_call(*printf, &args_values, "printf_call");
+                    .build_call(self.get_printf(), &args_values, "printf_call");
             }
             value => {
                 LOGGER.error(&format!(
@@ -0,0 +1,212 @@
+use inkwell::{
+    types::{AnyType, BasicTypeEnum, StructType},
+    values::{BasicValueEnum, FunctionValue, PointerValue},
+    AddressSpace,
+};
+
+use super::{expr_compiler::GenExpr, mangle::mangle, CodeGen, LOGGER};
+use crate::{
+    ast::{
+        ast::{Ast, Type, TypesArg},
+        expr::Expr,
+        function::Func,
+        struct_::Struct,
+    },
+    errors::logger::Log,
+    parser_lexer::{lexer::Span, symbol::Symbol},
+};
+
+pub trait GenStruct<'ctx> {
+    /// Declares every struct of the program as a named llvm struct
+    ///
+    /// This happens before any function is generated, so a function can use a struct that is
+    /// declared below it. A struct containing itself, directly or through another struct, has
+    /// no size and is a error.
+    fn gen_structs(&self, ast: &Ast);
+    /// The declaration of a struct together with its llvm type
+    fn get_struct(&self, name: Symbol) -> Option<(Struct, StructType<'ctx>)>;
+    /// The llvm type of a written type, None if it is a struct that doesn't exist
+    ///
+    /// Strings are a pointer to their chars, like they are when they are returned.
+    fn type_of(&self, type_: &TypesArg) -> Option<BasicTypeEnum<'ctx>>;
+    /// Generates a new value of a struct, the fields are generated in the order they are
+    /// written in.
+    ///
+    /// Every field of the struct needs a value and every value needs to match the type of its
+    /// field.
+    fn gen_struct_literal(
+        &self,
+        name: Symbol,
+        fields: &[(Symbol, Expr)],
+        span: Span,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, String>;
+    /// Returns the pointer to a field of the struct the pointer points to
+    fn gen_field_pointer(
+        &self,
+        value: PointerValue<'ctx>,
+        field: Symbol,
+        span: Span,
+    ) -> Result<PointerValue<'ctx>, String>;
+}
+
+trait StructLayout {
+    /// Checks if the struct ends up within itself through the fields of the given struct
+    fn contains(&self, inner: Symbol, outer: Symbol, seen: &mut Vec<Symbol>) -> bool;
+}
+
+impl<'ctx> StructLayout for CodeGen<'ctx> {
+    fn contains(&self, inner: Symbol, outer: Symbol, seen: &mut Vec<Symbol>) -> bool {
+        if seen.contains(&inner) {
+            return false;
+        }
+        seen.push(inner);
+        let Some((inner, _)) = self.get_struct(inner) else {
+            return false;
+        };
+        inner.fields.iter().any(|field| match field.type_ {
+            TypesArg::Struct(name) => name == outer || self.contains(name, outer, seen),
+            _ => false,
+        })
+    }
+}
+
+impl<'ctx> GenStruct<'ctx> for CodeGen<'ctx> {
+    fn gen_structs(&self, ast: &Ast) {
+        let structs = ast.body.iter().filter_map(|node| match &node.type_ {
+            Type::Struct(struct_) => Some(struct_),
+            _ => None,
+        });
+        // The types are declared before their fields are set, so fields can use any struct
+        let mut declared = Vec::new();
+        for struct_ in structs {
+            if self.get_struct(struct_.name).is_some() {
+                LOGGER.error(&format!(
+                    "Found a second struct named {}, on line {}",
+                    struct_.name, struct_.span.line
+                ));
+                continue;
+            }
+            let type_ = self.context.opaque_struct_type(&mangle(struct_.name));
+            self.structs
+                .borrow_mut()
+                .insert(struct_.name, (struct_.clone(), type_));
+            declared.push((struct_, type_));
+        }
+
+        for (struct_, type_) in declared {
+            if self.contains(struct_.name, struct_.name, &mut Vec::new()) {
+                LOGGER.error(&format!(
+                    "{} contains itself, on line {}",
+                    struct_.name, struct_.span.line
+                ));
+                continue;
+            }
+            let fields = struct_
+                .fields
+                .iter()
+                .map(|field| self.type_of(&field.type_).ok_or(field))
+                .collect::<Result<Vec<_>, _>>();
+            match fields {
+                Ok(fields) => {
+                    type_.set_body(&fields, false);
+                }
+                Err(field) => LOGGER.error(&format!(
+                    "The field {} of {} has the type {}, but {} isn't a struct, on line {}",
+                    field.name, struct_.name, field.type_, field.type_, field.span.line
+                )),
+            }
+        }
+    }
+    fn get_struct(&self, name: Symbol) -> Option<(Struct, StructType<'ctx>)> {
+        self.structs.borrow().get(&name).cloned()
+    }
+    fn type_of(&self, type_: &TypesArg) -> Option<BasicTypeEnum<'ctx>> {
+        let type_ = match type_ {
+            TypesArg::Number => self.context.i32_type().into(),
+            TypesArg::Char => self.context.i8_type().into(),
+            TypesArg::String => self
+                .context
+                .i8_type()
+                .ptr_type(AddressSpace::default())
+                .into(),
+            TypesArg::Struct(name) => self.get_struct(*name)?.1.into(),
+            TypesArg::None => return None,
+        };
+        Some(type_)
+    }
+    fn gen_struct_literal(
+        &self,
+        name: Symbol,
+        fields: &[(Symbol, Expr)],
+        span: Span,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let Some((struct_, type_)) = self.get_struct(name) else {
+            return Err(format!("{name} isn't a struct, on line {}", span.line));
+        };
+        if let Some(missing) = struct_
+            .fields
+            .iter()
+            .find(|field| fields.iter().all(|(written, _)| *written != field.name))
+        {
+            return Err(format!(
+                "The new {name} on line {} doesn't have a value for the field {}",
+                span.line, missing.name
+            ));
+        }
+
+        let pointer = self.build_entry_alloca(type_, "struct");
+        for (field, expr) in fields {
+            let Some(index) = struct_.field_index(*field) else {
+                return Err(format!(
+                    "{name} doesn't have a field named {field}, on line {}",
+                    expr.span.line
+                ));
+            };
+            let value = self.gen_expr(expr, function, func)?;
+            let field_type = type_
+                .get_field_type_at_index(index)
+                .ok_or_else(|| format!("{name} doesn't have a field named {field}"))?;
+            let Some(value) = self.gen_cast(value, field_type) else {
+                return Err(format!(
+                    "The field {field} of {name} has the type {} but its value has a different \
+                     type, on line {}",
+                    struct_.fields[index as usize].type_, expr.span.line
+                ));
+            };
+            let element = self.gen_field_pointer(pointer, *field, expr.span)?;
+            self.builder.build_store(element, value);
+        }
+        Ok(self.builder.build_load(pointer, &mangle(name)))
+    }
+    fn gen_field_pointer(
+        &self,
+        value: PointerValue<'ctx>,
+        field: Symbol,
+        span: Span,
+    ) -> Result<PointerValue<'ctx>, String> {
+        let type_ = value.get_type().get_element_type();
+        let structs = self.structs.borrow();
+        let Some((struct_, _)) = structs
+            .values()
+            .find(|(_, struct_type)| struct_type.as_any_type_enum() == type_)
+        else {
+            return Err(format!(
+                "Only structs have fields, found .{field} on line {}",
+                span.line
+            ));
+        };
+        let Some(index) = struct_.field_index(field) else {
+            return Err(format!(
+                "{} doesn't have a field named {field}, on line {}",
+                struct_.name, span.line
+            ));
+        };
+        self.builder
+            .build_struct_gep(value, index, &mangle(field))
+            .map_err(|_| format!("Couldn't find the field {field}, on line {}", span.line))
+    }
+}
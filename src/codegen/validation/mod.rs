@@ -23,6 +23,13 @@ pub fn compare_args(args_fn: Vec<BasicValueEnum>, args_input: Vec<BasicMetadataV
                     return false;
                 }
             }
+            BasicTypeEnum::StructType(struct_type) => {
+                if !arg_input.is_struct_value()
+                    || arg_input.into_struct_value().get_type() != struct_type
+                {
+                    return false;
+                }
+            }
             _ => return false,
         }
     }
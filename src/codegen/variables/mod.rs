@@ -1,48 +1,119 @@
 use crate::{ast::function::Func, errors::logger::Log};
 use colored::Colorize;
-use inkwell::values::{AnyValue, AnyValueEnum, FunctionValue};
+use inkwell::{
+    types::BasicTypeEnum,
+    values::{AnyValue, AnyValueEnum, BasicValueEnum, FunctionValue, PointerValue},
+};
 
-use crate::ast::variable::{TypeVar, Variable};
+use crate::ast::ast::TypesArg;
+use crate::ast::expr::{Expr, ExprKind};
+use crate::ast::variable::{Assign, AssignTarget, TypeVar, Variable};
+use crate::parser_lexer::symbol::Symbol;
 
 use super::{
-    get_args_function::Args, std_compiler::Std, validation::compare_args, CodeGen, LOGGER,
+    expr_compiler::GenExpr,
+    get_args_function::{get_int_value, Args}, mangle::mangle, std_compiler::Std,
+    struct_compiler::GenStruct, validation::compare_args, CodeGen, LOGGER,
 };
 
 pub trait GenVar<'ctx> {
     fn gen_variable(&self, variable: &Variable, function: &Func, func: &FunctionValue<'ctx>);
+    /// Stores the value of a assignment in the local it assigns to
+    ///
+    /// Constants are globals and can't be assigned to, only locals and arguments can.
+    fn gen_assign(
+        &self,
+        assign: &Assign,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<(), String>;
 }
 
 trait Gen<'ctx> {
     fn gen_num(&self, num: &i32, variable: &Variable);
+    fn gen_char(&self, char: &char, variable: &Variable);
     fn gen_string(&self, string: &str, variable: &Variable);
-    fn gen_call(&self, call: &Func, func: &FunctionValue<'ctx>, variable: &Variable);
+    fn gen_call(
+        &self,
+        call: &Func,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+        variable: &Variable,
+    );
+    fn gen_arr(&self, variable: &Variable, function: &Func, func: &FunctionValue<'ctx>);
+    fn gen_expression(
+        &self,
+        expr: &Expr,
+        function: &Func,
+        variable: &Variable,
+        func: &FunctionValue<'ctx>,
+    );
     fn gen_assign_identifier(
         &self,
-        id: &str,
+        id: Symbol,
         function: &Func,
         variable: &Variable,
         func: &FunctionValue<'ctx>,
     );
+    /// Stores a struct with every field set to zero, for a variable with a struct type but
+    /// without a value: let p: Point;
+    fn gen_zeroed(&self, variable: &Variable);
+    /// Stores the value in a new local for the variable
+    fn store_variable(&self, variable: &Variable, value: BasicValueEnum<'ctx>);
+    /// Turns the value into the type written after the name of the variable
+    ///
+    /// Calculations turn chars into numbers, so numbers, chars and booleans are converted into
+    /// each other. Any other difference in type is a error.
+    fn annotated_value(
+        &self,
+        variable: &Variable,
+        value: BasicValueEnum<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, String>;
+    /// The pointer a assignment stores its value in, for arrays this is the element
+    fn gen_assign_target(
+        &self,
+        assign: &Assign,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<PointerValue<'ctx>, String>;
 }
 
 impl<'ctx> Gen<'ctx> for CodeGen<'ctx> {
     fn gen_num(&self, num: &i32, variable: &Variable) {
         let i32_type = self.context.i32_type();
-        let var = self.builder.build_alloca(i32_type, &variable.name);
+        let var = self.build_local(i32_type, variable.name);
         self.builder
             .build_store(var, i32_type.const_int(*num as u64, false));
     }
+    fn gen_char(&self, char: &char, variable: &Variable) {
+        let i8_type = self.context.i8_type();
+        let var = self.build_local(i8_type, variable.name);
+        self.builder
+            .build_store(var, i8_type.const_int(*char as u64, false));
+    }
     fn gen_string(&self, str: &str, variable: &Variable) {
         let arr = self.context.i8_type().array_type(str.len() as u32);
-        let var = self.builder.build_alloca(arr, &variable.name);
+        let var = self.build_local(arr, variable.name);
         let arr_value = self.context.const_string(str.as_bytes(), false);
         let _ = self.builder.build_store(var, arr_value);
     }
-    fn gen_call(&self, call: &Func, func: &FunctionValue<'ctx>, variable: &Variable) {
-        let call_fn = self.module.get_function(&call.name);
-        let fn_args = CodeGen::get_args_value(self, call, func);
+    fn gen_call(
+        &self,
+        call: &Func,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+        variable: &Variable,
+    ) {
+        let call_fn = self.module.get_function(&mangle(call.name));
+        let fn_args = match self.get_args_value(call, function, func) {
+            Ok(args) => args,
+            Err(error) => {
+                LOGGER.error(&error);
+                return;
+            }
+        };
         if call_fn.is_none() {
-            let call_std = self.std_functions(call, fn_args, Some(&variable.name));
+            let call_std = self.std_functions(call, fn_args, Some(variable.name.as_str()));
             if call_std.is_ok() {
                 return;
             }
@@ -61,83 +132,261 @@ impl<'ctx> Gen<'ctx> for CodeGen<'ctx> {
                 call.name
             ));
         }
-        self.builder
-            .build_call(call_fn, &fn_args.to_owned(), &variable.name);
+        let result = self.builder.build_call(call_fn, &fn_args, "call");
+        match result.try_as_basic_value().left() {
+            Some(value) => self.store_variable(variable, value),
+            None => LOGGER.error(&format!(
+                "{} doesn't return a value, so {} can't be assigned to it.",
+                call.name, variable.name
+            )),
+        }
+    }
+    fn gen_arr(&self, variable: &Variable, function: &Func, func: &FunctionValue<'ctx>) {
+        let array = variable.type_.to_expr(variable.span).map(|expr| expr.kind);
+        let Some(ExprKind::Array(values)) = array else {
+            return;
+        };
+        let name = mangle(variable.name);
+        let array = match self.gen_array(&values, variable.span, &name, function, func) {
+            Ok(array) => array,
+            Err(error) => {
+                LOGGER.error(&error);
+                return;
+            }
+        };
+        self.scoped.borrow_mut().push((variable.name, array));
+        // The array stays in its local, a zeroed array of the same type is enough to check the
+        // type written after the name
+        let Ok(type_) = BasicTypeEnum::try_from(array.get_type().get_element_type()) else {
+            return;
+        };
+        if let Err(error) = self.annotated_value(variable, type_.const_zero()) {
+            LOGGER.error(&error);
+        }
+    }
+    fn gen_expression(
+        &self,
+        expr: &Expr,
+        function: &Func,
+        variable: &Variable,
+        func: &FunctionValue<'ctx>,
+    ) {
+        match self.gen_expr(expr, function, func) {
+            Ok(value) => self.store_variable(variable, value),
+            Err(error) => LOGGER.error(&error),
+        }
     }
     fn gen_assign_identifier(
         &self,
-        id: &str,
+        id: Symbol,
         function: &Func,
         variable: &Variable,
         func: &FunctionValue<'ctx>,
     ) {
-        let item = func.get_first_basic_block();
-        if let Some(block) = item {
-            let var = block.get_instruction_with_name(id);
-            match var {
-                Some(ins) => {
-                    let ins = ins.as_any_value_enum();
-                    match ins {
-                        AnyValueEnum::IntValue(int) => {
-                            let value = self.context.i32_type();
-                            let var = self.builder.build_alloca(value, &variable.name);
-                            let _ = self.builder.build_store(var, int);
-                        }
-                        // Still kinda need to think about how I want to handle this
-                        // &value  || something like that
-                        // I am not sure yet
-                        AnyValueEnum::PointerValue(pointer) => {
-                            let _ = self.builder.build_load(pointer, &variable.name);
-                        }
-                        _ => (),
-                    }
-                }
-                None => {
-                    let arg = function.get_arg_index_(id);
-                    if let Some(arg) = arg {
-                        let func_arg = func.get_nth_param(arg);
-                        if let Some(arg) = func_arg {
-                            let arg = arg.as_any_value_enum();
-                            match arg {
-                                inkwell::values::AnyValueEnum::IntValue(value) => {
-                                    let int = self.context.i32_type();
-                                    let var = self.builder.build_alloca(int, &variable.name);
-                                    let _ = &self.builder.build_store(var, value);
-                                }
-                                inkwell::values::AnyValueEnum::PointerValue(value) => {
-                                    let _ = &self.builder.build_load(value, &variable.name);
-                                }
-                                _ => todo!("Not yet supported argument type"),
+        match self.get_local(id) {
+            Some(AnyValueEnum::IntValue(int)) => self.store_variable(variable, int.into()),
+            // Locals are stored behind a pointer, the new variable gets a copy of the value
+            Some(AnyValueEnum::PointerValue(pointer)) => {
+                let value = self.builder.build_load(pointer, &mangle(id));
+                self.store_variable(variable, value);
+            }
+            Some(_) => (),
+            None => {
+                let arg = function.get_arg_index_(id);
+                if let Some(arg) = arg {
+                    let func_arg = func.get_nth_param(arg);
+                    if let Some(arg) = func_arg {
+                        let arg = arg.as_any_value_enum();
+                        match arg {
+                            inkwell::values::AnyValueEnum::IntValue(value) => {
+                                // Arguments can be both numbers and chars
+                                let var = self.build_local(value.get_type(), variable.name);
+                                let _ = &self.builder.build_store(var, value);
                             }
-                        } else {
-                            LOGGER.error(&"Found argument but not at index.");
+                            inkwell::values::AnyValueEnum::PointerValue(value) => {
+                                // The new variable points at the same value as the argument
+                                self.store_variable(variable, value.into());
+                            }
+                            _ => todo!("Not yet supported argument type"),
                         }
                     } else {
-                        LOGGER.error(&format!(
-                            "tried assigning {}, to {}, but {} doesn't exist within this scope.",
-                            id.blue().bold(),
-                            variable.name.bold().yellow(),
-                            id.blue().bold()
-                        ));
+                        LOGGER.error(&"Found argument but not at index.");
                     }
+                } else {
+                    LOGGER.error(&format!(
+                        "tried assigning {}, to {}, but {} doesn't exist within this scope.",
+                        id.as_str().blue().bold(),
+                        variable.name.as_str().bold().yellow(),
+                        id.as_str().blue().bold()
+                    ));
                 }
             }
         }
     }
+    fn gen_zeroed(&self, variable: &Variable) {
+        let Some(TypesArg::Struct(name)) = variable.annotation else {
+            return;
+        };
+        match self.get_struct(name) {
+            Some((_, type_)) => {
+                let var = self.build_local(type_, variable.name);
+                self.builder.build_store(var, type_.const_zero());
+            }
+            None => LOGGER.error(&format!(
+                "{} has the type {name}, but {name} isn't a struct, on line {}",
+                variable.name, variable.line
+            )),
+        }
+    }
+    fn store_variable(&self, variable: &Variable, value: BasicValueEnum<'ctx>) {
+        match self.annotated_value(variable, value) {
+            // Stored in a local so the variable can be found from anywhere within the function
+            Ok(value) => {
+                let var = self.build_local(value.get_type(), variable.name);
+                self.builder.build_store(var, value);
+            }
+            Err(error) => LOGGER.error(&error),
+        }
+    }
+    fn annotated_value(
+        &self,
+        variable: &Variable,
+        value: BasicValueEnum<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, String> {
+        let Some(annotation) = &variable.annotation else {
+            return Ok(value);
+        };
+        let mismatch = || {
+            format!(
+                "{} has the type {annotation} but its value has a different type, on line {}",
+                variable.name, variable.line
+            )
+        };
+        let int_type = match annotation {
+            TypesArg::Number => self.context.i32_type(),
+            TypesArg::Char => self.context.i8_type(),
+            // Strings are either a array of chars or a pointer to the chars
+            TypesArg::String => {
+                return match value.get_type() {
+                    BasicTypeEnum::ArrayType(array)
+                        if array.get_element_type() == self.context.i8_type().into() =>
+                    {
+                        Ok(value)
+                    }
+                    BasicTypeEnum::PointerType(_) => Ok(value),
+                    _ => Err(mismatch()),
+                }
+            }
+            TypesArg::Struct(name) => {
+                return match self.get_struct(*name) {
+                    Some((_, type_)) if value.get_type() == type_.into() => Ok(value),
+                    Some(_) => Err(mismatch()),
+                    None => Err(format!(
+                        "{} has the type {name}, but {name} isn't a struct, on line {}",
+                        variable.name, variable.line
+                    )),
+                }
+            }
+            TypesArg::None => return Ok(value),
+        };
+
+        let Some(int) = get_int_value(value) else {
+            return Err(mismatch());
+        };
+        // A boolean is 0 or 1, sign extending it would turn true into -1
+        let value = if int.get_type().get_bit_width() == 1 {
+            self.builder.build_int_z_extend(int, int_type, "cast")
+        } else {
+            self.builder.build_int_cast(int, int_type, "cast")
+        };
+        Ok(value.into())
+    }
+    fn gen_assign_target(
+        &self,
+        assign: &Assign,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<PointerValue<'ctx>, String> {
+        let name = assign.target.name();
+        let Some(AnyValueEnum::PointerValue(local)) = self.get_local(name) else {
+            if self.module.get_global(&mangle(name)).is_some() {
+                return Err(format!(
+                    "Can't assign to {name} on line {}, {name} is a constant.",
+                    assign.span.line
+                ));
+            }
+            return Err(format!(
+                "tried assigning to {name} on line {}, but {name} doesn't exist within this scope.",
+                assign.span.line
+            ));
+        };
+
+        match &assign.target {
+            AssignTarget::Variable(_) => Ok(local),
+            AssignTarget::Index { array, index } => {
+                let array = self.gen_array_pointer(array, function, func)?;
+                self.gen_element_pointer(array, index, function, func)
+            }
+            AssignTarget::Field { value, field } => {
+                let value = self.gen_place(value, function, func)?;
+                self.gen_field_pointer(value, *field, assign.span)
+            }
+        }
+    }
 }
 
 impl<'ctx> GenVar<'ctx> for CodeGen<'ctx> {
     fn gen_variable(&self, variable: &Variable, function: &Func, func: &FunctionValue<'ctx>) {
         match &variable.type_ {
-            // For arrays we need to make sure that all types within the array are the same type
-            TypeVar::Arr { .. } => {}
+            TypeVar::Arr { .. } => self.gen_arr(variable, function, func),
             TypeVar::Number(value) => {
                 self.gen_num(value, variable);
             }
             TypeVar::String(value) => self.gen_string(value, variable),
-            TypeVar::Identifier(id) => self.gen_assign_identifier(id, function, variable, func),
-            TypeVar::FunctionCall(call) => self.gen_call(call, func, variable),
-            TypeVar::None => {}
+            TypeVar::Char(value) => self.gen_char(value, variable),
+            TypeVar::Identifier(id) => self.gen_assign_identifier(*id, function, variable, func),
+            TypeVar::FunctionCall(call) => self.gen_call(call, function, func, variable),
+            TypeVar::Expr(expr) => self.gen_expression(expr, function, variable, func),
+            TypeVar::None => self.gen_zeroed(variable),
+        }
+    }
+    fn gen_assign(
+        &self,
+        assign: &Assign,
+        function: &Func,
+        func: &FunctionValue<'ctx>,
+    ) -> Result<(), String> {
+        let name = assign.target.name();
+        let pointer = self.gen_assign_target(assign, function, func)?;
+        let mut value = self.gen_expr(&assign.value, function, func)?;
+
+        if let Some(op) = assign.op {
+            let current = self.builder.build_load(pointer, &mangle(name));
+            let (Some(current), Some(rhs)) = (get_int_value(current), get_int_value(value)) else {
+                return Err(format!(
+                    "Only numbers, chars and booleans can be used in calculations, on line {}",
+                    assign.span.line
+                ));
+            };
+            let result = self.gen_operation(op, current, rhs);
+            // c += 1 keeps c a char even though 1 is a number
+            value = self
+                .builder
+                .build_int_cast(result, current.get_type(), "cast")
+                .into();
         }
+
+        // Like annotated variables, numbers, chars and booleans are converted into each other
+        let value = BasicTypeEnum::try_from(pointer.get_type().get_element_type())
+            .ok()
+            .and_then(|type_| self.gen_cast(value, type_));
+        let Some(value) = value else {
+            return Err(format!(
+                "Can't assign a value of a different type to {name} on line {}",
+                assign.span.line
+            ));
+        };
+        self.builder.build_store(pointer, value);
+        Ok(())
     }
 }
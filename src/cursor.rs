@@ -3,86 +3,148 @@
 pub mod cursor {
     /// The cursor struct responsible for iterating over the input string
     /// It will keep track of the current position and the previous char
+    ///
+    /// The cursor borrows the input instead of copying it, the position is a byte offset into
+    /// the input. Next to the position it also keeps track of the current line and column, so the
+    /// lexer can give every token an exact span.
     #[derive(Clone)]
-    pub struct Cursor {
-        chars: Vec<char>,
+    pub struct Cursor<'a> {
+        input: &'a str,
         position: usize,
+        line: usize,
+        column: usize,
         previous: Option<char>,
     }
 
-    impl Iterator for Cursor {
+    impl<'a> Cursor<'a> {
+        /// The input that hasn't been consumed yet
+        fn rest(&self) -> &'a str {
+            self.input.get(self.position..).unwrap_or("")
+        }
+
+        /// Moves the position forward by n chars, keeping the line and column in sync.
+        fn forward(&mut self, n: usize) {
+            for _ in 0..n {
+                let Some(char) = self.rest().chars().next() else {
+                    return;
+                };
+                self.position += char.len_utf8();
+                if char == '\n' {
+                    self.line += 1;
+                    self.column = 1;
+                } else {
+                    self.column += 1;
+                }
+            }
+        }
+    }
+
+    impl<'a> Iterator for Cursor<'a> {
         type Item = char;
 
         fn next(&mut self) -> Option<Self::Item> {
-            let prev = self.chars.get(self.position)?;
-            self.previous = Some(*prev);
-            self.position += 1;
-            Some(*prev)
+            let prev = self.rest().chars().next()?;
+            self.previous = Some(prev);
+            self.forward(1);
+            Some(prev)
         }
 
         fn nth(&mut self, n: usize) -> Option<Self::Item> {
-            let prev = self.chars.get(self.position)?;
-            self.previous = Some(*prev);
-            self.position += n + 1;
-            Some(*prev)
+            let prev = self.rest().chars().next()?;
+            self.previous = Some(prev);
+            self.forward(n + 1);
+            Some(prev)
         }
     }
 
     /// The cursor iterator trait
     /// It is used to create a new cursor and move the cursor
-    pub trait CursorIter {
-        fn new(input: String) -> Cursor;
+    pub trait CursorIter<'a> {
+        fn new(input: &'a str) -> Cursor<'a>;
         fn peak_nth(&self, n: usize) -> Option<Vec<char>>;
-        fn peek(&self) -> Option<&char>;
+        fn peek(&self) -> Option<char>;
         fn position(&self) -> usize;
+        fn line(&self) -> usize;
+        fn column(&self) -> usize;
         fn previous(&self) -> Option<char>;
         fn advance_pos(&mut self, n: usize);
         fn advance_back(&mut self, n: usize);
+        fn slice(&self, start: usize, end: usize) -> &'a str;
     }
 
-    impl CursorIter for Cursor {
+    impl<'a> CursorIter<'a> for Cursor<'a> {
         /// Create a new cursor from a string
-        fn new(input: String) -> Cursor {
-            let chars = input.chars().collect();
+        fn new(input: &'a str) -> Cursor<'a> {
             Cursor {
-                chars,
+                input,
                 position: 0,
+                line: 1,
+                column: 1,
                 previous: None,
             }
         }
 
-        /// Peak the next char this will not advance the position of the cursor therefore not
-        /// consuming the char
+        /// Peak the next n chars this will not advance the position of the cursor therefore not
+        /// consuming the chars
         fn peak_nth(&self, n: usize) -> Option<Vec<char>> {
-            if self.position + n >= self.chars.len() {
+            let chars: Vec<char> = self.rest().chars().take(n).collect();
+            if chars.len() < n {
                 return None;
             }
-            Some(self.chars[self.position..(self.position + n)].to_vec())
+            Some(chars)
         }
 
         /// Peak the next char this will not advance the position of the cursor therefore not
         /// consume the char
-        fn peek(&self) -> Option<&char> {
-            return self.chars.get(self.position);
+        fn peek(&self) -> Option<char> {
+            self.rest().chars().next()
         }
 
-        /// Get the current position of the cursor
+        /// Get the current position of the cursor, this is the byte offset within the input
         fn position(&self) -> usize {
             self.position
         }
 
+        /// Get the line the cursor is currently on, starting at 1
+        fn line(&self) -> usize {
+            self.line
+        }
+
+        /// Get the column of the cursor on the current line, starting at 1
+        fn column(&self) -> usize {
+            self.column
+        }
+
         /// Get the previous character
         fn previous(&self) -> Option<char> {
             self.previous
         }
 
         fn advance_pos(&mut self, n: usize) {
-            self.position += n;
+            self.forward(n);
         }
 
         fn advance_back(&mut self, n: usize) {
-            let pos = self.position - n;
-            self.position = pos;
+            let mut crossed_line = false;
+            for char in self.input[..self.position].chars().rev().take(n) {
+                self.position -= char.len_utf8();
+                if char == '\n' {
+                    self.line -= 1;
+                    crossed_line = true;
+                } else {
+                    self.column = self.column.saturating_sub(1);
+                }
+            }
+            // If we walked back over a newline the column has to be counted again
+            if crossed_line {
+                let line_start = self.input[..self.position].rfind('\n').map_or(0, |i| i + 1);
+                self.column = self.input[line_start..self.position].chars().count() + 1;
+            }
+        }
+
+        /// Get the input between two byte offsets
+        fn slice(&self, start: usize, end: usize) -> &'a str {
+            self.input.get(start..end).unwrap_or("")
         }
     }
 }
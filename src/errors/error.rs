@@ -1,5 +1,7 @@
 use std::fmt::Display;
 
+use crate::parser_lexer::{lexer::Span, source_map::file_name};
+
 /// ErrorBuilder,
 ///
 /// It allows for building error messages based on:
@@ -43,6 +45,9 @@ pub trait BuildError {
     fn message(&mut self, mess: impl AsRef<str>) -> &mut Self;
     fn helper(&mut self, help_message: impl AsRef<str>) -> &mut Self;
     fn line(&mut self, line: usize) -> &mut Self;
+    /// Sets the line and the file name using the span, the file name is looked up in the source
+    /// map.
+    fn span(&mut self, span: Span) -> &mut Self;
     /// Used to build the error into a string
     fn build(&mut self) -> String;
     /// Used to build the error into a non mutuable version of the error
@@ -78,6 +83,12 @@ impl BuildError for ErrorBuilder {
         self.line = line;
         self
     }
+    fn span(&mut self, span: Span) -> &mut Self {
+        if let Some(name) = span.file.and_then(file_name) {
+            self.file_name = name;
+        }
+        self.line(span.line)
+    }
     fn build(&mut self) -> String {
         format!("{self}")
     }
@@ -1,6 +1,10 @@
 use colored::Colorize;
 
-use crate::parser_lexer::lexer::{Operators, Token};
+use crate::ast::ast::TypesArg;
+use crate::parser_lexer::{
+    lexer::{LexError, Operators, Span, Token},
+    symbol::Symbol,
+};
 
 use super::error::{BuildError, ErrorBuilder};
 
@@ -11,14 +15,13 @@ use super::error::{BuildError, ErrorBuilder};
 /// - let some = "wow"
 ///      -> Missing semicolon
 ///      -> Helper consider adding a semicolon
-pub fn non_ending_variable(var: String, line: usize) -> ErrorBuilder {
+pub fn non_ending_variable(var: Symbol, span: Span) -> ErrorBuilder {
     ErrorBuilder::new()
         .message(format!(
             "Found a variable without and ending semicolon {}",
             var
         ))
-        .line(line)
-        .file_name("todo:.rs")
+        .span(span)
         .helper(format!(
             "Consider adding a semicolon: let {} = var {}",
             "--> ; <--".blue().bold(),
@@ -27,6 +30,80 @@ pub fn non_ending_variable(var: String, line: usize) -> ErrorBuilder {
         .build_error()
 }
 
+/// The error for a colon after the name of a variable that isn't followed by a type
+///
+/// - let count: 0;
+///   -> The types are number, string and char
+pub fn invalid_type_annotation(found: Option<Symbol>, span: Span) -> ErrorBuilder {
+    let message = match found {
+        Some(found) => format!("Expected a type after the colon but found {found}"),
+        None => "Expected a type after the colon but the file ended".to_string(),
+    };
+    ErrorBuilder::new()
+        .message(message)
+        .helper(format!(
+            "The types are: {}, {}, {} and the name of a struct",
+            "number".yellow().bold(),
+            "string".yellow().bold(),
+            "char".yellow().bold()
+        ))
+        .span(span)
+        .build_error()
+}
+
+/// The error for a variable whose value doesn't match the type written after its name
+///
+/// - let count: number = "one";
+///   -> count has the type number but its value is a string
+pub fn mismatched_type_annotation(
+    var: Symbol,
+    annotation: &TypesArg,
+    found: &TypesArg,
+    span: Span,
+) -> ErrorBuilder {
+    ErrorBuilder::new()
+        .message(format!(
+            "{var} has the type {annotation} but its value is a {found}"
+        ))
+        .span(span)
+        .build_error()
+}
+
+/// The error for a variable without a value and without a type, its type can't be inferred
+///
+/// - let count;
+///   -> Consider adding a type: let count: number;
+pub fn missing_type_annotation(var: Symbol, span: Span) -> ErrorBuilder {
+    ErrorBuilder::new()
+        .message(format!(
+            "The type of {var} can't be inferred because it doesn't have a value"
+        ))
+        .helper(format!(
+            "Consider adding a type: let {var}{}",
+            ": number;".yellow().bold()
+        ))
+        .span(span)
+        .build_error()
+}
+
+/// The error for a variable whose value is a identifier or call without a type, the type of
+/// those values isn't known while parsing
+///
+/// - let count = other;
+///   -> Consider adding a type: let count: number = other;
+pub fn missing_value_annotation(var: Symbol, span: Span) -> ErrorBuilder {
+    ErrorBuilder::new()
+        .message(format!(
+            "The type of {var} can't be inferred from a variable or function call"
+        ))
+        .helper(format!(
+            "Consider adding a type: let {var}{}",
+            ": number = ...;".yellow().bold()
+        ))
+        .span(span)
+        .build_error()
+}
+
 /// Invalid function syntax
 ///
 /// Invalid function syntax would look something like:
@@ -37,11 +114,10 @@ pub fn non_ending_variable(var: String, line: usize) -> ErrorBuilder {
 /// - fn
 ///     -> fn doesn't mean anything it doesn't have a function body or identifier therefore can't
 ///     be parsed.
-pub fn invalid_function_syntax_missing_id(line: usize) -> ErrorBuilder {
+pub fn invalid_function_syntax_missing_id(span: Span) -> ErrorBuilder {
     ErrorBuilder::new()
         .message("Found invalid function syntax.")
-        .line(line)
-        .file_name("todo:.rs")
+        .span(span)
         .helper(format!(
             "Consider adding a identifier to the function: {} {}",
             "fn".blue().bold(),
@@ -60,11 +136,10 @@ pub fn invalid_function_syntax_missing_id(line: usize) -> ErrorBuilder {
 /// - fn
 ///     -> fn doesn't mean anything it doesn't have a function body or identifier therefore can't
 ///     be parsed.
-pub fn invalid_function_body_syntax(name: String, line: usize) -> ErrorBuilder {
+pub fn invalid_function_body_syntax(name: Symbol, span: Span) -> ErrorBuilder {
     ErrorBuilder::new()
         .message("Found invalid function syntax.")
-        .line(line)
-        .file_name("todo:")
+        .span(span)
         .helper(format!(
             "Consider adding a body to the function -> fn {name} {}{}{}",
             "{".blue().bold(),
@@ -74,83 +149,211 @@ pub fn invalid_function_body_syntax(name: String, line: usize) -> ErrorBuilder {
         .build_error()
 }
 
+/// The error for tokens the lexer couldn't make sense of
+///
+/// The value of the invalid token is the source it was lexed from, the lexer error itself is
+/// reported through [`lex_error`]:
+///
+/// - let a = $;
+///   -> Found invalid syntax: $
+pub fn invalid_token(token: Token) -> ErrorBuilder {
+    ErrorBuilder::new()
+        .message(format!("Found invalid syntax: {}", token.value))
+        .span(token.span)
+        .build_error()
+}
+
+/// The error for tokens that can't start a statement where they were found
+///
+/// - fn main() { ) }
+///   -> Found ) where a statement was expected
+pub fn unexpected_token(token: Token) -> ErrorBuilder {
+    ErrorBuilder::new()
+        .message(format!(
+            "Found {} where a statement was expected",
+            token.value
+        ))
+        .span(token.span)
+        .build_error()
+}
+
+/// The error for keywords in the arguments of a function that aren't a type
+///
+/// - fn some(let a) {}
+///   -> let isn't a type a argument can have
+pub fn invalid_argument_type(token: Token) -> ErrorBuilder {
+    ErrorBuilder::new()
+        .message(format!("{} isn't a type a argument can have", token.value))
+        .helper(format!(
+            "Arguments can be a {}, {} or {}",
+            "number".yellow().bold(),
+            "string".yellow().bold(),
+            "char".yellow().bold()
+        ))
+        .span(token.span)
+        .build_error()
+}
+
+/// The error for mistakes found by the lexer
+///
+/// - "this string never ends
+///   -> Found a string without a closing quote at column 1
+pub fn lex_error(error: &LexError) -> ErrorBuilder {
+    let mut builder = ErrorBuilder::new();
+    builder
+        .message(format!("{} at column {}", error.kind, error.span.column))
+        .span(error.span);
+    if let Some(suggestion) = &error.suggestion {
+        builder.helper(suggestion);
+    }
+    builder.build_error()
+}
+
+/// The error for number literals that don't fit in a number
+///
+/// - let big = 99999999999;
+///   -> Numbers are 32 bit so the biggest number is 2147483647
+pub fn number_out_of_range(number: Symbol, span: Span) -> ErrorBuilder {
+    ErrorBuilder::new()
+        .message(format!("The number {number} is to big to fit in a number"))
+        .helper(format!(
+            "Numbers range from {} to {}, consider using a smaller number",
+            i32::MIN.to_string().yellow().bold(),
+            i32::MAX.to_string().yellow().bold()
+        ))
+        .span(span)
+        .build_error()
+}
+
 pub fn invalid_var_syntax_token(token: Token) -> ErrorBuilder {
     ErrorBuilder::new()
         .message(format!(
             "Invalid syntax found {} while parsing variable",
             token.value
         ))
-        .line(token.line)
-        .file_name("todo:")
+        .span(token.span)
         .build_error()
 }
 
-pub fn invalid_function_call(name: String, line: usize) -> ErrorBuilder {
+pub fn invalid_function_call(name: Symbol, span: Span) -> ErrorBuilder {
     ErrorBuilder::new()
         .message(format!(
             "Invalid function call ${name}",
         ))
-        .line(line)
-        .helper(format!("Found a function call to {}  with no leading OpenBrace en CloseBrace consider changing it to {}(...).", name.yellow().bold(), name.blue().bold()))
-        .file_name("todo:")
+        .span(span)
+        .helper(format!("Found a function call to {}  with no leading OpenBrace en CloseBrace consider changing it to {}(...).", name.as_str().yellow().bold(), name.as_str().blue().bold()))
         .build_error()
 }
 
-pub fn invalid_arr_no_end(line: usize) -> ErrorBuilder {
+pub fn invalid_arr_no_end(span: Span) -> ErrorBuilder {
     ErrorBuilder::new()
         .message("Invalid syntax found for array")
         .helper(format!(
             "consider adding a end to the array {}",
             "]".yellow().bold()
         ))
-        .line(line)
-        .file_name("todo:")
+        .span(span)
         .build_error()
 }
 
-pub fn invalid_return_no_end(line: usize) -> ErrorBuilder {
+pub fn invalid_return_no_end(span: Span) -> ErrorBuilder {
     ErrorBuilder::new()
         .message("Invalid syntax found for return")
         .helper(format!(
             "consider adding a end to the return statement: {}",
             ";".yellow().bold()
         ))
-        .line(line)
-        .file_name("todo:")
+        .span(span)
         .build_error()
 }
 
-pub fn invalid_if_statement_operator(token: Operators) -> ErrorBuilder {
+pub fn invalid_if_statement_operator(token: Operators, span: Span) -> ErrorBuilder {
     ErrorBuilder::new()
         .message("Found a invalid if statement operator")
         .helper(format!("Found: {:#?}", token))
-        .file_name("todo:")
+        .span(span)
         .build_error()
 }
-pub fn invalid_if_statement_body(_line: usize) -> ErrorBuilder {
+pub fn invalid_if_statement_body(span: Span) -> ErrorBuilder {
     ErrorBuilder::new()
+        .span(span)
         .message("Found a invalid if statement, the if statement doesn't have a body.")
         .helper(format!(
             "Consider adding  a body: {:#?}",
             "-> { <<body>> } <-".bold().yellow()
         ))
-        .file_name("todo:")
         .build_error()
 }
-pub fn invalid_use(file: Option<String>, line: usize) -> ErrorBuilder {
+/// The error for a while loop without a body
+///
+/// - while a < 10
+///   -> Consider adding a body
+pub fn invalid_while_body(span: Span) -> ErrorBuilder {
+    ErrorBuilder::new()
+        .span(span)
+        .message("Found a invalid while loop, the loop doesn't have a body.")
+        .helper(format!(
+            "Consider adding a body: {}",
+            "-> { <<body>> } <-".bold().yellow()
+        ))
+        .build_error()
+}
+/// The error for a for loop that is missing its variable, the in keyword or its body
+///
+/// - for i 0..10 { }
+///   -> A for loop looks like: for i in 0..10 { }
+pub fn invalid_for_loop(span: Span) -> ErrorBuilder {
+    ErrorBuilder::new()
+        .span(span)
+        .message("Found a invalid for loop")
+        .helper(format!(
+            "A for loop looks like: {}",
+            "for i in 0..10 { <<body>> }".bold().yellow()
+        ))
+        .build_error()
+}
+/// The error for a statement starting with a identifier that is neither a call nor a assignment
+///
+/// - a == 1;
+///   -> Consider assigning a value: a = 1;
+pub fn invalid_assignment(name: Symbol, span: Span) -> ErrorBuilder {
+    ErrorBuilder::new()
+        .span(span)
+        .message(format!("Found a invalid assignment to {name}"))
+        .helper(format!(
+            "Consider assigning a value: {}",
+            format!("{name} = 1;").bold().yellow()
+        ))
+        .build_error()
+}
+/// The error for a break or continue without a semicolon
+///
+/// - break
+///   -> Consider adding a semicolon
+pub fn non_ending_statement(statement: Symbol, span: Span) -> ErrorBuilder {
+    ErrorBuilder::new()
+        .message(format!("Found a {statement} without a ending semicolon"))
+        .helper(format!(
+            "Consider adding a semicolon: {statement}{}",
+            ";".blue().bold()
+        ))
+        .span(span)
+        .build_error()
+}
+pub fn invalid_use(file: Option<Symbol>, span: Span) -> ErrorBuilder {
     match file {
         Some(file) => ErrorBuilder::new()
-            .file_name(format!("Found invalid use statement: use {file}"))
+            .message(format!("Found invalid use statement: use {file}"))
             .helper("Consider adding and ending semicolon")
-            .line(line)
+            .span(span)
             .build_error(),
         None => ErrorBuilder::new()
-            .file_name("Found invalid use statement: no filename was found")
+            .message("Found invalid use statement: no filename was found")
             .helper(format!(
                 "Consider adding a file name: {}",
                 "use \"file\"".bold().yellow()
             ))
-            .line(line)
+            .span(span)
             .build_error(),
     }
 }
@@ -161,12 +364,105 @@ pub fn invalid_use(file: Option<String>, line: usize) -> ErrorBuilder {
 ///
 /// Here there is no close so we return the error with the helper: Consider ending a closing brace
 /// - (string hello, number hello ...
-pub fn args_no_close(file: Option<String>, line: usize) -> ErrorBuilder {
-    let mut builder = ErrorBuilder::new();
-    if let Some(file) = file {
-        builder.file_name(file);
-    }
-    builder.line(line);
-    builder.helper("Add a closing brace -> ) <- to the end of the listed arguments");
-    builder.build_error()
+pub fn args_no_close(span: Span) -> ErrorBuilder {
+    ErrorBuilder::new()
+        .message("Found function arguments without a closing brace")
+        .helper("Add a closing brace -> ) <- to the end of the listed arguments")
+        .span(span)
+        .build_error()
+}
+
+/// The error for a token that can't be the start of a value
+///
+/// - let a = 1 + ;
+///   -> Expected a value but found ;
+pub fn invalid_expression(token: Token) -> ErrorBuilder {
+    ErrorBuilder::new()
+        .message(format!("Expected a value but found {}", token.value))
+        .helper(format!(
+            "Consider adding a value such as a number, identifier or call: {}",
+            "1 + a * add(b, c)".yellow().bold()
+        ))
+        .span(token.span)
+        .build_error()
+}
+
+/// The error for a value that is missing because the file ended
+///
+/// - let a = 1 +
+///   -> Expected a value but the file ended
+pub fn missing_expression(span: Span) -> ErrorBuilder {
+    ErrorBuilder::new()
+        .message("Expected a value but the file ended")
+        .span(span)
+        .build_error()
+}
+
+/// The error for a parenthesised expression that is never closed
+///
+/// - let a = (1 + 2;
+///   -> Add a closing brace
+pub fn expression_no_close(span: Span) -> ErrorBuilder {
+    ErrorBuilder::new()
+        .message("Found a expression without a closing brace")
+        .helper(format!(
+            "Add a closing brace {} to the end of the expression",
+            "-> ) <-".yellow().bold()
+        ))
+        .span(span)
+        .build_error()
+}
+
+/// The error for a struct declaration that is missing its name, braces or the type of a field
+///
+/// - struct Point { x, y }
+///   -> A struct looks like: struct Point { x: number, y: number }
+pub fn invalid_struct(span: Span) -> ErrorBuilder {
+    ErrorBuilder::new()
+        .message("Found a invalid struct declaration")
+        .helper(format!(
+            "A struct looks like: {}",
+            "struct Point { x: number, y: number }".yellow().bold()
+        ))
+        .span(span)
+        .build_error()
+}
+
+/// The error for a field that is declared or given a value more than once
+///
+/// - struct Point { x: number, x: number }
+///   -> Point already has a field named x
+pub fn duplicate_field(name: Symbol, field: Symbol, span: Span) -> ErrorBuilder {
+    ErrorBuilder::new()
+        .message(format!("{name} already has a field named {field}"))
+        .helper("Every field can only be written once, consider renaming one of them")
+        .span(span)
+        .build_error()
+}
+
+/// The error for a struct literal that is missing a brace, colon or the value of a field
+///
+/// - let p = Point { x: 1 y: 2 };
+///   -> A new Point looks like: Point { x: 1, y: 2 }
+pub fn invalid_struct_literal(name: Symbol, span: Span) -> ErrorBuilder {
+    ErrorBuilder::new()
+        .message(format!("Found a invalid value of {name}"))
+        .helper(format!(
+            "A new {name} looks like: {}",
+            format!("{name} {{ x: 1, y: 2 }}").yellow().bold()
+        ))
+        .span(span)
+        .build_error()
+}
+
+/// The error for a dot that isn't followed by the name of a field
+///
+/// - let x = p.;
+///   -> Expected the name of a field after the dot
+pub fn invalid_field_access(span: Span) -> ErrorBuilder {
+    ErrorBuilder::new()
+        .message("Expected the name of a field after the dot")
+        .helper(format!("A field is read like: {}", "p.x".yellow().bold()))
+        .span(span)
+        .build_error()
 }
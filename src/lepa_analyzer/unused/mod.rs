@@ -8,6 +8,7 @@ use crate::{
     },
     codegen::LOGGER,
     errors::logger::Log,
+    parser_lexer::symbol::Symbol,
 };
 
 use self::{unused_args::DetectUnusedArgs, unused_fn::DetectUnusedFunc, unused_vars::DetectorVars};
@@ -20,7 +21,7 @@ pub mod unused_vars;
 pub enum UnusedValues<'a> {
     Variable(&'a Variable),
     Function(&'a Func),
-    Argument(&'a Arg, &'a str),
+    Argument(&'a Arg, Symbol),
 }
 
 struct DetectUnused<'a> {
@@ -2,14 +2,17 @@
 #![allow(unused_assignments)]
 
 use super::{DetectUnused, UnusedValues};
-use crate::ast::{
-    ast::{Arg, Ast, Type},
-    variable::TypeVar,
+use crate::{
+    ast::{
+        ast::{Arg, Ast, Type},
+        variable::TypeVar,
+    },
+    parser_lexer::symbol::Symbol,
 };
 
 #[derive(Debug)]
 pub struct DetectedArg<'a> {
-    func_name: &'a str,
+    func_name: Symbol,
     arg: &'a Arg,
     block: &'a Ast,
 }
@@ -17,7 +20,7 @@ pub struct DetectedArg<'a> {
 pub trait DetectUnusedArgs<'a> {
     fn detect_arg(&self) -> Vec<DetectedArg<'a>>;
     fn detect_unused_args(&self) -> Vec<UnusedValues<'a>>;
-    fn detect_unused_args_block(block: &'a Ast, value: &str) -> bool {
+    fn detect_unused_args_block(block: &'a Ast, value: Symbol) -> bool {
         for token in &block.body {
             match &token.type_ {
                 Type::Variable(var) => {
@@ -31,7 +34,17 @@ pub trait DetectUnusedArgs<'a> {
                     }
                 }
                 Type::Return(ret) => {
-                    if ret.value == value {
+                    if ret.uses(value) {
+                        return false;
+                    }
+                }
+                Type::For(for_) => {
+                    if for_.iter.uses(value) {
+                        return false;
+                    }
+                }
+                Type::Assign(assign) => {
+                    if assign.uses(value) {
                         return false;
                     }
                 }
@@ -43,7 +56,7 @@ pub trait DetectUnusedArgs<'a> {
 }
 
 impl<'a> DetectedArg<'a> {
-    pub fn new(arg: &'a Arg, block: &'a Ast, func_name: &'a str) -> DetectedArg<'a> {
+    pub fn new(arg: &'a Arg, block: &'a Ast, func_name: Symbol) -> DetectedArg<'a> {
         Self {
             func_name,
             arg,
@@ -53,7 +66,7 @@ impl<'a> DetectedArg<'a> {
     pub fn multiple(
         args_: &'a Vec<Arg>,
         block: &'a Ast,
-        func_name: &'a str,
+        func_name: Symbol,
     ) -> Vec<DetectedArg<'a>> {
         let mut args = Vec::new();
         for arg in args_ {
@@ -74,7 +87,7 @@ impl<'a> DetectUnusedArgs<'a> for DetectUnused<'a> {
                         vec.append(&mut DetectedArg::multiple(
                             &func.args,
                             func.body.as_ref().unwrap(),
-                            &func.name,
+                            func.name,
                         ));
                     }
                 }
@@ -90,18 +103,18 @@ impl<'a> DetectUnusedArgs<'a> for DetectUnused<'a> {
             let mut used = false;
             match &item.block.type_ {
                 Type::FunctionCall(call) => {
-                    if call.args_uses(&item.arg.value) {
+                    if call.args_uses(item.arg.value) {
                         used = true;
                     }
                 }
                 Type::Variable(var) => match &var.type_ {
                     TypeVar::Identifier(id) => {
-                        if id == &item.arg.value {
+                        if *id == item.arg.value {
                             used = true;
                         }
                     }
                     TypeVar::FunctionCall(call) => {
-                        if call.args_uses(&item.arg.value) {
+                        if call.args_uses(item.arg.value) {
                             used = true;
                         }
                     }
@@ -109,13 +122,13 @@ impl<'a> DetectUnusedArgs<'a> for DetectUnused<'a> {
                 },
                 Type::Function(func) => {
                     if let Some(body) = &func.body {
-                        if !DetectUnused::detect_unused_args_block(body, &item.arg.value) {
+                        if !DetectUnused::detect_unused_args_block(body, item.arg.value) {
                             used = true;
                         }
                     }
                 }
                 Type::Block => {
-                    if !DetectUnused::detect_unused_args_block(item.block, &item.arg.value) {
+                    if !DetectUnused::detect_unused_args_block(item.block, item.arg.value) {
                         used = true;
                     }
                 }
@@ -1,4 +1,9 @@
-use crate::ast::{ast::Type, function::Func, variable::TypeVar};
+use std::collections::HashSet;
+
+use crate::{
+    ast::{ast::Type, function::Func, variable::TypeVar},
+    parser_lexer::symbol::Symbol,
+};
 
 use super::{DetectUnused, UnusedValues};
 
@@ -15,8 +20,8 @@ impl<'a> DetectedFunc<'a> {
 
 pub trait DetectUnusedFunc<'a> {
     fn detect_func(&self) -> Vec<DetectedFunc<'a>>;
-    // returns a vec with names of all used funcs
-    fn detect_used_funcs(&self) -> Vec<&'a String>;
+    // returns a set with names of all used funcs
+    fn detect_used_funcs(&self) -> HashSet<Symbol>;
     fn detect_unused_funcs(&self) -> Vec<UnusedValues<'a>>;
 }
 
@@ -38,35 +43,56 @@ impl<'a> DetectUnusedFunc<'a> for DetectUnused<'a> {
         }
         found_funcs
     }
-    fn detect_used_funcs(&self) -> Vec<&'a String> {
-        let mut found_funcs: Vec<&'a String> = Vec::new();
+    fn detect_used_funcs(&self) -> HashSet<Symbol> {
+        let mut found_funcs = HashSet::new();
         for token in &self.ast.body {
             match &token.type_ {
-                Type::Variable(var) => {
-                    if let TypeVar::FunctionCall(call) = &var.type_ {
-                        let get_found = found_funcs.iter().find(|x| **x == &call.name);
-                        if get_found.is_none() {
-                            found_funcs.push(&call.name);
-                        }
+                Type::Variable(var) => match &var.type_ {
+                    TypeVar::FunctionCall(call) => {
+                        found_funcs.insert(call.name);
+                        found_funcs.extend(call.arg_calls().iter().map(|call| call.name));
                     }
-                }
-                Type::FunctionCall(call) => {
-                    let get_found = found_funcs.iter().find(|x| **x == &call.name);
-                    if get_found.is_none() {
-                        found_funcs.push(&call.name);
+                    TypeVar::Expr(expr) => {
+                        found_funcs.extend(expr.calls().iter().map(|call| call.name));
                     }
+                    _ => (),
+                },
+                Type::FunctionCall(call) => {
+                    found_funcs.insert(call.name);
+                    found_funcs.extend(call.arg_calls().iter().map(|call| call.name));
+                }
+                Type::Return(ret) => {
+                    found_funcs.extend(ret.calls().iter().map(|call| call.name));
                 }
                 Type::Function(func) => {
                     if func.body.is_some() {
                         let detector = DetectUnused::new(func.body.as_ref().unwrap());
-                        let mut detected = detector.detect_used_funcs();
-                        found_funcs.append(&mut detected);
+                        found_funcs.extend(detector.detect_used_funcs());
                     }
                 }
                 Type::Block => {
                     let detector = DetectUnused::new(token);
-                    let mut detected = detector.detect_used_funcs();
-                    found_funcs.append(&mut detected);
+                    found_funcs.extend(detector.detect_used_funcs());
+                }
+                Type::Logic(logic) => {
+                    found_funcs.extend(logic.condition_calls().iter().map(|call| call.name));
+                    for branch in logic.branches() {
+                        let detector = DetectUnused::new(branch);
+                        found_funcs.extend(detector.detect_used_funcs());
+                    }
+                }
+                Type::While(while_) => {
+                    found_funcs.extend(while_.condition.calls().iter().map(|call| call.name));
+                    let detector = DetectUnused::new(&while_.body);
+                    found_funcs.extend(detector.detect_used_funcs());
+                }
+                Type::Assign(assign) => {
+                    found_funcs.extend(assign.calls().iter().map(|call| call.name));
+                }
+                Type::For(for_) => {
+                    found_funcs.extend(for_.iter.calls().iter().map(|call| call.name));
+                    let detector = DetectUnused::new(&for_.body);
+                    found_funcs.extend(detector.detect_used_funcs());
                 }
                 _ => (),
             }
@@ -82,8 +108,8 @@ impl<'a> DetectUnusedFunc<'a> for DetectUnused<'a> {
                 "main" => {
                     continue;
                 }
-                name => {
-                    if !used.contains(&&(name.to_string())) {
+                _ => {
+                    if !used.contains(&func.func.name) {
                         unused.push(UnusedValues::Function(func.func));
                     }
                 }
@@ -1,12 +1,9 @@
 use crate::ast::ast::Ast;
-use crate::ast::ast::Case;
-use crate::ast::ast::ReturnTypes;
 use crate::ast::ast::Type;
-use crate::ast::ast::TypesArg;
-use crate::ast::variable::TypeVar;
 use crate::lepa_analyzer::unused::DetectUnused;
 use crate::lepa_analyzer::unused::UnusedValues;
 use crate::lepa_analyzer::Variable;
+use crate::parser_lexer::symbol::Symbol;
 
 #[derive(Debug)]
 pub struct DetectedVar<'a> {
@@ -23,7 +20,7 @@ impl<'a> DetectedVar<'a> {
 pub trait DetectorVars<'a> {
     fn detect_vars(&self) -> Vec<DetectedVar<'a>>;
     fn detect_unused_vars(&self) -> Vec<UnusedValues<'a>>;
-    fn detect_used_in_block_var(&self, value: &str) -> bool;
+    fn detect_used_in_block_var(&self, value: Symbol) -> bool;
 }
 
 impl<'a> DetectorVars<'a> for DetectUnused<'a> {
@@ -50,17 +47,20 @@ impl<'a> DetectorVars<'a> for DetectUnused<'a> {
                     vars.append(&mut func_vars);
                 }
                 Type::Logic(logic) => {
-                    let logic_if = DetectUnused::new(&logic.do_);
-                    let mut logic_vars_if = logic_if.detect_vars();
-                    vars.append(&mut logic_vars_if);
-
-                    if logic.else_.is_some() {
-                        let else_ = &logic.else_.as_ref().unwrap();
-                        let else_ = DetectUnused::new(else_);
-                        let mut else_vars = else_.detect_vars();
-                        vars.append(&mut else_vars);
+                    for branch in logic.branches() {
+                        let branch = DetectUnused::new(branch);
+                        let mut branch_vars = branch.detect_vars();
+                        vars.append(&mut branch_vars);
                     }
                 }
+                Type::While(while_) => {
+                    let body = DetectUnused::new(&while_.body);
+                    vars.append(&mut body.detect_vars());
+                }
+                Type::For(for_) => {
+                    let body = DetectUnused::new(&for_.body);
+                    vars.append(&mut body.detect_vars());
+                }
                 _ => (),
             }
         }
@@ -74,73 +74,73 @@ impl<'a> DetectorVars<'a> for DetectUnused<'a> {
             let mut used = false;
             for token in &detected_var.block.body {
                 match &token.type_ {
-                    Type::Variable(var) => match &var.type_ {
-                        TypeVar::Identifier(id) => {
-                            if *id == detected_var.var.name {
-                                used = true;
-                                break;
-                            }
+                    Type::Variable(var) => {
+                        if var.type_.uses(detected_var.var.name) {
+                            used = true;
+                            break;
                         }
-                        _ => (),
-                    },
+                    }
                     Type::Block => {
                         let func = DetectUnused::new(token);
-                        if func.detect_used_in_block_var(&detected_var.var.name) {
+                        if func.detect_used_in_block_var(detected_var.var.name) {
                             used = true;
                             break;
                         };
                     }
                     Type::Function(_) => {
                         let func = DetectUnused::new(token);
-                        if func.detect_used_in_block_var(&detected_var.var.name) {
+                        if func.detect_used_in_block_var(detected_var.var.name) {
                             used = true;
                             break;
                         };
                     }
                     Type::Return(val) => {
-                        if val.type_ == ReturnTypes::Identifier && val.value == detected_var.var.name {
+                        if val.uses(detected_var.var.name) {
                             used = true;
                             break;
                         }
                     }
                     Type::Logic(logic) => {
-                        for l in &logic.if_ {
-                            match l {
-                                Case::EqEq(val1, val2) => {
-                                    let false = val1.uses(&detected_var.var.name) else {
-                                        used = true; 
-                                        break;
-                                    };
-                                    let false = val2.uses(&detected_var.var.name) else {
-                                        used = true; 
-                                        break;
-                                    };
-
-                                    let block = DetectUnused::new(&logic.do_);
-                                    if block.detect_used_in_block_var(&detected_var.var.name) {
-                                        used = true;
-                                        break;
-                                    };
-
-                                    if logic.else_.is_some() {
-                                        let else_ = &logic.else_.as_ref().unwrap();
-                                        let block = DetectUnused::new(else_);
-                                        if block.detect_used_in_block_var(&detected_var.var.name) {
-                                            used = true;
-                                            break;
-                                        };
-                                    }
-                                }
-                                _ => (),
-                            }
+                        let name = detected_var.var.name;
+                        let in_branch = logic
+                            .branches()
+                            .into_iter()
+                            .any(|branch| DetectUnused::new(branch).detect_used_in_block_var(name));
+                        if logic.conditions_use(name) || in_branch {
+                            used = true;
+                            break;
+                        }
+                    }
+                    Type::While(while_) => {
+                        let name = detected_var.var.name;
+                        let body = DetectUnused::new(&while_.body);
+                        if while_.condition.uses(name) || body.detect_used_in_block_var(name) {
+                            used = true;
+                            break;
+                        }
+                    }
+                    Type::For(for_) => {
+                        let name = detected_var.var.name;
+                        // Within the body the name refers to the loop variable if they are the same
+                        let body = DetectUnused::new(&for_.body);
+                        let in_body = for_.var != name && body.detect_used_in_block_var(name);
+                        if for_.iter.uses(name) || in_body {
+                            used = true;
+                            break;
                         }
                     }
                     Type::FunctionCall(call) => {
-                        if call.args_uses(&detected_var.var.name) {
+                        if call.args_uses(detected_var.var.name) {
                             used = true;
                             break;
                         };
                     }
+                    Type::Assign(assign) => {
+                        if assign.uses(detected_var.var.name) {
+                            used = true;
+                            break;
+                        }
+                    }
                     _ => continue,
                 }
             }
@@ -151,20 +151,15 @@ impl<'a> DetectorVars<'a> for DetectUnused<'a> {
         unused_vars
     }
 
-    fn detect_used_in_block_var(&self, value: &str) -> bool {
+    fn detect_used_in_block_var(&self, value: Symbol) -> bool {
         for token in &self.ast.body {
             match &token.type_ {
-                Type::Variable(var) => match &var.type_ {
-                    TypeVar::Identifier(id) if id == value => {
-                        return true;
-                    }
-                    _ => continue,
-                },
+                Type::Variable(var) if var.type_.uses(value) => {
+                    return true;
+                }
                 Type::Function(func) => {
-                    for arg in &func.args {
-                        if arg.type_ == TypesArg::None && arg.value == value {
-                            return true;
-                        }
+                    if func.args_uses(value) {
+                        return true;
                     }
                     if func.body.is_some() {
                         let body = func.body.as_ref().unwrap();
@@ -173,7 +168,7 @@ impl<'a> DetectorVars<'a> for DetectUnused<'a> {
                     }
                 }
                 Type::Return(val) => {
-                        if val.type_ == ReturnTypes::Identifier && val.value == value {
+                        if val.uses(value) {
                             return true;
                         }
                 }
@@ -181,6 +176,35 @@ impl<'a> DetectorVars<'a> for DetectUnused<'a> {
                     let body = DetectUnused::new(token);
                     return body.detect_used_in_block_var(value);
                 }
+                Type::FunctionCall(call) if call.args_uses(value) => {
+                    return true;
+                }
+                Type::Assign(assign) if assign.uses(value) => {
+                    return true;
+                }
+                Type::Logic(logic) => {
+                    if logic.conditions_use(value) {
+                        return true;
+                    }
+                    for branch in logic.branches() {
+                        if DetectUnused::new(branch).detect_used_in_block_var(value) {
+                            return true;
+                        }
+                    }
+                }
+                Type::While(while_) => {
+                    let body = DetectUnused::new(&while_.body);
+                    if while_.condition.uses(value) || body.detect_used_in_block_var(value) {
+                        return true;
+                    }
+                }
+                Type::For(for_) => {
+                    let body = DetectUnused::new(&for_.body);
+                    let in_body = for_.var != value && body.detect_used_in_block_var(value);
+                    if for_.iter.uses(value) || in_body {
+                        return true;
+                    }
+                }
                 _ => continue,
             }
         }
@@ -1,5 +1,5 @@
 use lepa::{ast::ast::Ast, codegen::Compile, lepa_analyzer::unused::Unused, logme};
-use std::{fs, io::Write};
+use std::{env, fs, io::Write};
 
 use lepa::{
     ast::use_::{CompileUses, GetUses, Use},
@@ -9,29 +9,43 @@ use lepa::{
 
 fn compile() {
     let files = fs::read_to_string("./main.lp");
-    let mut lexer = Token::lex(files.unwrap());
-    let parse = Parser::new(lexer.clone()).parse();
+    let (mut lexer, lex_errors) = Token::lex_file("./main.lp", files.unwrap());
+    let (parse, errors) = Parser::new(lexer.clone())
+        .with_lex_errors(lex_errors)
+        .parse_all();
 
-    if parse.is_err() {
-        println!("{}", parse.err().unwrap());
+    if !errors.is_empty() {
+        errors.iter().for_each(|error| println!("{}", error));
         return;
     }
 
-    let parse = parse.unwrap();
     let unused = Ast::find_unused(&parse);
     Ast::log_unused(unused);
 
     let uses = Use::get_use(&parse).unwrap();
     let compile_uses = uses.compile().unwrap();
     let mut tokens_now = Vec::new();
+    let mut errors_now = Vec::new();
     for mut tokens in compile_uses {
         tokens_now.append(&mut tokens.contents);
+        errors_now.append(&mut tokens.errors);
     }
     tokens_now.append(&mut lexer);
 
-    let parse = Parser::new(tokens_now).parse().unwrap();
+    let (parse, errors) = Parser::new(tokens_now)
+        .with_lex_errors(errors_now)
+        .parse_all();
+    if !errors.is_empty() {
+        errors.iter().for_each(|error| println!("{}", error));
+        return;
+    }
 
-    let compile = parse.compile();
+    // Checking every index of a array while the program runs is opt in
+    let compile = if env::args().any(|arg| arg == "--bounds-checks") {
+        parse.compile_with_bounds_checks()
+    } else {
+        parse.compile()
+    };
     let main_file = std::fs::File::create("./target/main");
     match main_file {
         Ok(mut file) => {
@@ -1,4 +1,10 @@
+use std::fmt::Display;
+
+use unicode_ident::{is_xid_continue, is_xid_start};
+
 use crate::cursor::cursor::{Cursor, CursorIter};
+use crate::parser_lexer::source_map::{load_file, FileId};
+use crate::parser_lexer::symbol::Symbol;
 
 /// The tokeentypes that are used in the lexer
 #[derive(Debug, PartialEq, Clone)]
@@ -6,9 +12,12 @@ pub enum TokenType {
     Identifier,
     Number,
     String,
+    Char,
     Operator(Operators),
     Keyword(KeyWords),
     Dot,
+    /// `..` used for ranges such as `0..10`
+    DotDot,
     Comma,
     Space,
     SemiColon,
@@ -16,6 +25,9 @@ pub enum TokenType {
     Invalid,
     Min,
     Plus,
+    Star,
+    Percent,
+    Not,
     OpenBrace,
     CloseBrace,
     OpenBracket,
@@ -23,6 +35,7 @@ pub enum TokenType {
     OpenCurlyBracket,
     CloseCurlyBracket,
     Comment,
+    DocComment,
     Slash,
     Or,
     OrOr,
@@ -39,189 +52,582 @@ pub enum KeyWords {
     Bool,
     While,
     For,
+    In,
+    Break,
+    Continue,
     Return,
     Number,
     String,
+    Char,
     Use,
     Const,
+    Struct,
 }
 
 /// All the operators
 #[derive(Debug, PartialEq, Clone)]
 pub enum Operators {
     EqEq,
+    NotEq,
     Eq,
+    PlusEq,
+    MinEq,
+    StarEq,
+    SlashEq,
+    PercentEq,
     Less,
     LessEq,
     More,
     MoreEq,
-    Invalid(Box<[char]>),
+}
+
+/// The location of a token or ast node in the source.
+///
+/// Start and end are byte offsets into the input, end being exclusive. Line and column point at
+/// the first char and both start at 1. The file is the file in the source map the span belongs
+/// to, input lexed without a file has no file.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+    pub file: Option<FileId>,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize, line: usize, column: usize) -> Span {
+        Span {
+            start,
+            end,
+            line,
+            column,
+            file: None,
+        }
+    }
+
+    /// Assign the file the span belongs to
+    pub fn in_file(mut self, file: Option<FileId>) -> Span {
+        self.file = file;
+        self
+    }
+
+    /// Creates a span reaching from the start of self up until the end of other.
+    pub fn to(&self, other: &Span) -> Span {
+        Span {
+            start: self.start,
+            end: other.end,
+            line: self.line,
+            column: self.column,
+            file: self.file,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Token {
     pub token_type: TokenType,
-    pub value: String,
+    pub value: Symbol,
     pub line: usize,
+    pub span: Span,
 }
 
 impl Token {
     pub fn new(token_type: TokenType, value: impl AsRef<str>, line: usize) -> Token {
         return Token {
             token_type,
-            value: value.as_ref().to_owned(),
+            value: Symbol::intern(value.as_ref()),
             line,
+            span: Span {
+                line,
+                ..Span::default()
+            },
+        };
+    }
+
+    /// Assign the span of the token, the line of the span is kept in sync with the token line
+    pub fn with_span(mut self, span: Span) -> Token {
+        self.line = span.line;
+        self.span = span;
+        self
+    }
+
+    /// The file the token was lexed from
+    pub fn file(&self) -> Option<FileId> {
+        self.span.file
+    }
+}
+
+/// The kind of mistake the lexer found in the input
+#[derive(Debug, PartialEq, Clone)]
+pub enum LexErrorKind {
+    UnterminatedString,
+    UnterminatedRawString,
+    RawStringWithoutQuote,
+    InvalidEscape(String),
+    EmptyChar,
+    UnterminatedChar,
+    MultipleChars,
+    WideChar(char),
+    UnterminatedComment,
+    InvalidDigit { number: String, digit: char },
+    MissingDigits(String),
+    UnknownChar(char),
+}
+
+impl LexErrorKind {
+    /// A suggestion on how the error could be resolved
+    pub fn suggestion(&self) -> Option<String> {
+        let suggestion = match self {
+            LexErrorKind::UnterminatedString => "Add a \" at the end of the string",
+            LexErrorKind::UnterminatedRawString => {
+                "Close the raw string with a \" followed by as many # as it started with"
+            }
+            LexErrorKind::RawStringWithoutQuote => "Raw strings start with r\" or r#\"",
+            LexErrorKind::InvalidEscape(_) => {
+                "Valid escapes are \\n \\t \\r \\0 \\\\ \\\" \\' \\xNN up to \\x7F and \\u{NNNNNN}"
+            }
+            LexErrorKind::EmptyChar => "Put a character between the quotes, for example 'a'",
+            LexErrorKind::UnterminatedChar => "Add a ' after the character",
+            LexErrorKind::MultipleChars | LexErrorKind::WideChar(_) => {
+                "Consider using a string instead, for example \"ab\""
+            }
+            LexErrorKind::UnterminatedComment => "Every /* has to be closed by its own */",
+            LexErrorKind::InvalidDigit { .. } => {
+                "Hex numbers use 0-9 and a-f, octal numbers 0-7 and binary numbers 0 and 1"
+            }
+            LexErrorKind::MissingDigits(_) => "Add digits after the prefix, for example 0x1F",
+            LexErrorKind::UnknownChar(_) => return None,
         };
+        Some(suggestion.to_string())
+    }
+}
+
+impl Display for LexErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LexErrorKind::UnterminatedString => write!(f, "Found a string without a closing quote"),
+            LexErrorKind::UnterminatedRawString => {
+                write!(f, "Found a raw string without a closing quote")
+            }
+            LexErrorKind::RawStringWithoutQuote => {
+                write!(f, "Found a raw string without an opening quote")
+            }
+            LexErrorKind::InvalidEscape(escape) => {
+                write!(f, "Found an invalid escape \\{escape} in a string")
+            }
+            LexErrorKind::EmptyChar => write!(f, "Found a empty character literal ''"),
+            LexErrorKind::UnterminatedChar => {
+                write!(f, "Found a character literal without a closing quote")
+            }
+            LexErrorKind::MultipleChars => {
+                write!(f, "Found a character literal with more than one character")
+            }
+            LexErrorKind::WideChar(char) => write!(
+                f,
+                "Found a character literal '{char}' that doesn't fit in a single byte"
+            ),
+            LexErrorKind::UnterminatedComment => {
+                write!(f, "Found a block comment without a closing */")
+            }
+            LexErrorKind::InvalidDigit { number, digit } => {
+                write!(
+                    f,
+                    "Found a invalid digit {digit} in the number {number}{digit}"
+                )
+            }
+            LexErrorKind::MissingDigits(prefix) => {
+                write!(f, "Found the number prefix {prefix} without any digits")
+            }
+            LexErrorKind::UnknownChar(char) => write!(f, "Found the unknown character {char}"),
+        }
+    }
+}
+
+/// A error found while lexing, the lexer keeps going after a error so every error in a file can
+/// be reported at once.
+///
+/// The tokens are left with a [`TokenType::Invalid`] token where the error was found, every
+/// lexer error is fatal so the parser won't run on them.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LexError {
+    pub kind: LexErrorKind,
+    pub span: Span,
+    pub suggestion: Option<String>,
+}
+
+impl LexError {
+    pub fn new(kind: LexErrorKind) -> LexError {
+        LexError {
+            suggestion: kind.suggestion(),
+            kind,
+            span: Span::default(),
+        }
+    }
+
+    /// Assign the span of the part of the input the error was found in
+    pub fn with_span(mut self, span: Span) -> LexError {
+        self.span = span;
+        self
     }
 }
 
+/// Identifiers start with a unicode XID_Start char or a _, the std functions start with a @
+fn is_identifier_start(char: char) -> bool {
+    char == '_' || char == '@' || is_xid_start(char)
+}
+
 pub trait Lexer {
     fn lex(input: String) -> Vec<Token>;
+    fn lex_with_errors(input: String) -> (Vec<Token>, Vec<LexError>);
+    fn lex_file(name: &str, input: String) -> (Vec<Token>, Vec<LexError>);
+    fn stream(input: &str) -> Tokens<'_>;
+    fn lossless(input: &str) -> Lossless<'_>;
 }
 
 pub trait Tokenizer {
+    fn with_eq_token(
+        &mut self,
+        l: usize,
+        single: (TokenType, &str),
+        with_eq: (Operators, &str),
+    ) -> Token;
     fn eq_token(&mut self, l: usize) -> Token;
     fn less_token(&mut self, l: usize) -> Token;
     fn more_token(&mut self, l: usize) -> Token;
-    fn string_token(&mut self, l: usize) -> Token;
-    //fn char_token(&mut self, l: usize) -> Token;
-    fn comment_token(&mut self, l: usize) -> Token;
-    fn number_token(&mut self, l: usize) -> Token;
+    fn string_token(&mut self, l: usize) -> Result<Token, LexError>;
+    fn raw_string_token(&mut self, l: usize) -> Result<Token, LexError>;
+    fn escape_sequence(&mut self) -> Result<char, LexError>;
+    fn escaped_char(&mut self) -> Result<char, String>;
+    fn char_token(&mut self, l: usize) -> Result<Token, LexError>;
+    fn comment_token(&mut self, l: usize) -> Result<Token, LexError>;
+    fn number_token(&mut self, l: usize) -> Result<Token, LexError>;
+    fn symbol_token(&mut self, char: char, l: usize) -> Result<Token, LexError>;
     fn identifier_token(&mut self, l: usize) -> Token;
     fn or_token(&mut self, l: usize) -> Token;
     fn and_token(&mut self, l: usize) -> Token;
+    fn dot_token(&mut self, l: usize) -> Token;
     fn keyword_token(t: impl AsRef<str>, l: usize) -> Option<Token>;
 }
 
-impl Tokenizer for Cursor {
+impl<'a> Tokenizer for Cursor<'a> {
+    /// Returns the operator combined with = if the next char is a =, for example + and +=
+    ///
+    /// This doesn't depend on whitespace so a<=b and a <= b lex the same.
+    fn with_eq_token(
+        &mut self,
+        l: usize,
+        single: (TokenType, &str),
+        with_eq: (Operators, &str),
+    ) -> Token {
+        if self.peek() == Some('=') {
+            // Advance the position by one to consume the =
+            self.next();
+            return Token::new(TokenType::Operator(with_eq.0), with_eq.1, l);
+        }
+        Token::new(single.0, single.1, l)
+    }
     /// My language will not support === cause that is for js
     fn eq_token(&mut self, l: usize) -> Token {
-        // If there is no char next it must only be a single =
-        // Therefore it is a single equal
-        let Some(peak) = self.peak_nth(1) else {
-            return Token::new(TokenType::Operator(Operators::Eq), "=", l);
-        };
-
-        let peak = &peak[0..1];
-        match peak {
-            ['='] => {
-                // Advance the position by one to consume the next char
-                self.advance_pos(1);
-                Token::new(TokenType::Operator(Operators::EqEq), "==", l)
-            }
-            [' '] => Token::new(TokenType::Operator(Operators::Eq), "=", l),
-            _ => Token::new(TokenType::Operator(Operators::Eq), "=", l),
-        }
+        self.with_eq_token(
+            l,
+            (TokenType::Operator(Operators::Eq), "="),
+            (Operators::EqEq, "=="),
+        )
     }
     fn less_token(&mut self, l: usize) -> Token {
-        // If there is no char next it must only be a single <
-        let Some(peak) = self.peak_nth(1) else {
-            return Token::new(TokenType::Operator(Operators::Less), "<", l);
-        };
-
-        match peak[0..1] {
-            ['='] => {
-                // Advance the position by one to consume the next char
-                self.advance_pos(1);
-                Token::new(TokenType::Operator(Operators::LessEq), "<=", l)
-            }
-            [' '] => Token::new(TokenType::Operator(Operators::Less), "<", l),
-            _ => Token::new(
-                TokenType::Operator(Operators::Invalid(Box::from(peak))),
-                "Invalid less operator",
-                l,
-            ),
-        }
+        self.with_eq_token(
+            l,
+            (TokenType::Operator(Operators::Less), "<"),
+            (Operators::LessEq, "<="),
+        )
     }
     fn more_token(&mut self, l: usize) -> Token {
-        // If there is no char next it must only be a single >
-        let Some(peak) = self.peak_nth(1) else {
-            return Token::new(TokenType::Operator(Operators::More),  ">",l);
-        };
-
-        match peak[0..1] {
-            ['='] => {
-                // Advance the position by 1 to consume the next char
-                self.advance_pos(1);
-                Token::new(TokenType::Operator(Operators::MoreEq), "==", l)
-            }
-            [' '] => Token::new(TokenType::Operator(Operators::More), ">", l),
-            _ => Token::new(
-                TokenType::Operator(Operators::Invalid(Box::from(peak))),
-                "Invalid more operator",
-                l,
-            ),
-        }
+        self.with_eq_token(
+            l,
+            (TokenType::Operator(Operators::More), ">"),
+            (Operators::MoreEq, ">="),
+        )
     }
     /// Returns the token type and the value
-    fn string_token(&mut self, l: usize) -> Token {
+    ///
+    /// Escape sequences are decoded, so the value holds the actual characters
+    fn string_token(&mut self, l: usize) -> Result<Token, LexError> {
         let mut string = String::new();
+        let mut invalid_escape = None;
         // Advance until we find a "
-        for char in self.by_ref() {
+        while let Some(char) = self.next() {
             match char {
-                '"' => return Token::new(TokenType::String, string, l),
+                '"' => {
+                    // We still read up until the closing quote so the rest of the file lexes fine
+                    if let Some(error) = invalid_escape {
+                        return Err(error);
+                    }
+                    return Ok(Token::new(TokenType::String, string, l));
+                }
+                '\\' => match self.escape_sequence() {
+                    Ok(escaped) => string.push(escaped),
+                    Err(error) => {
+                        invalid_escape.get_or_insert(error);
+                    }
+                },
                 _ => string.push(char),
             }
         }
         // If we get here it means we did not find a closing quote
         // This would be considered an error
-        Token::new(
-            TokenType::Invalid,
-            "Found a string without a closing quote",
-            l,
-        )
+        Err(LexError::new(LexErrorKind::UnterminatedString))
     }
-    // Char token 'c'..
-    // fn char_token(&mut self, l: usize) -> Token {
-    //     match self.next() {
-    //         Some(char) => match char {
-    //         },
-    //         _ => (),
-    //     }
-    // }
-    /// Returns the comment token type and the comment
-    /// Could also just return a slash if the /  isn't followed by two slashes
-    fn comment_token(&mut self, l: usize) -> Token {
-        let mut comment = String::new();
-        let Some(peak_next) = self.peak_nth(1) else {
-            return Token::new(TokenType::Slash, "/", l);
+    /// Returns a raw string, raw strings don't decode escape sequences
+    ///
+    /// r"C:\path" or r#"a "quoted" word"# , the amount of # has to match on both ends
+    fn raw_string_token(&mut self, l: usize) -> Result<Token, LexError> {
+        let mut hashes = 0;
+        while self.peek() == Some('#') {
+            self.next();
+            hashes += 1;
+        }
+        if self.next() != Some('"') {
+            return Err(LexError::new(LexErrorKind::RawStringWithoutQuote));
+        }
+
+        let mut string = String::new();
+        while let Some(char) = self.next() {
+            if char == '"' {
+                let closing = self
+                    .peak_nth(hashes)
+                    .map_or(hashes == 0, |peak| peak.iter().all(|char| *char == '#'));
+                if closing {
+                    self.advance_pos(hashes);
+                    return Ok(Token::new(TokenType::String, string, l));
+                }
+            }
+            string.push(char);
+        }
+        Err(LexError::new(LexErrorKind::UnterminatedRawString))
+    }
+    /// Decodes the escape sequence after a \ inside of a string
+    ///
+    /// Supported are: \n \t \r \0 \\ \" \' \xNN (up to 7F) and \u{NNNNNN}
+    fn escape_sequence(&mut self) -> Result<char, LexError> {
+        // The backslash has already been consumed, it is always a single byte
+        let start = CursorIter::position(self) - 1;
+        let (line, column) = (CursorIter::line(self), CursorIter::column(self) - 1);
+        self.escaped_char().map_err(|escape| {
+            // The error points at the escape instead of the whole string
+            let span = Span::new(start, CursorIter::position(self), line, column);
+            LexError::new(LexErrorKind::InvalidEscape(escape)).with_span(span)
+        })
+    }
+    /// Decodes the chars after the backslash, returns the written escape if it isn't valid
+    fn escaped_char(&mut self) -> Result<char, String> {
+        let invalid = |escape: &str| escape.to_string();
+
+        let Some(char) = self.next() else {
+            return Err(invalid(""));
+        };
+        match char {
+            'n' => Ok('\n'),
+            't' => Ok('\t'),
+            'r' => Ok('\r'),
+            '0' => Ok('\0'),
+            '\\' => Ok('\\'),
+            '"' => Ok('"'),
+            '\'' => Ok('\''),
+            'x' => {
+                let Some(digits) = self.peak_nth(2) else {
+                    return Err(invalid("x"));
+                };
+                let digits: String = digits.iter().collect();
+                match u8::from_str_radix(&digits, 16) {
+                    Ok(byte) if byte <= 0x7F => {
+                        self.advance_pos(2);
+                        Ok(byte as char)
+                    }
+                    _ => Err(invalid(&format!("x{digits}"))),
+                }
+            }
+            'u' => {
+                if self.peek() != Some('{') {
+                    return Err(invalid("u"));
+                }
+                self.next();
+                let mut digits = String::new();
+                while let Some(char) = self.next() {
+                    if char == '}' {
+                        return u32::from_str_radix(&digits, 16)
+                            .ok()
+                            .filter(|_| digits.len() <= 6)
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| invalid(&format!("u{{{digits}}}")));
+                    }
+                    if !char.is_ascii_hexdigit() {
+                        self.advance_back(1);
+                        break;
+                    }
+                    digits.push(char);
+                }
+                Err(invalid(&format!("u{{{digits}")))
+            }
+            char => Err(invalid(&char.to_string())),
+        }
+    }
+    /// Char token 'c', the char may also be a escape sequence '\n'
+    ///
+    /// Chars are a single byte, so only ascii characters are allowed.
+    fn char_token(&mut self, l: usize) -> Result<Token, LexError> {
+        let char = match self.next() {
+            Some('\'') => return Err(LexError::new(LexErrorKind::EmptyChar)),
+            Some('\\') => self.escape_sequence()?,
+            Some('\n') | None => return Err(LexError::new(LexErrorKind::UnterminatedChar)),
+            Some(char) => char,
         };
 
-        match peak_next[0..1] {
-            ['/'] => {
+        match self.peek() {
+            Some('\'') => {
                 self.next();
+            }
+            Some('\n') | None => return Err(LexError::new(LexErrorKind::UnterminatedChar)),
+            Some(_) => {
+                // Consume the rest of the literal so the other chars don't turn into tokens
+                while let Some(char) = self.peek() {
+                    match char {
+                        '\'' => {
+                            self.next();
+                            return Err(LexError::new(LexErrorKind::MultipleChars));
+                        }
+                        '\n' => break,
+                        _ => {
+                            self.next();
+                        }
+                    }
+                }
+                return Err(LexError::new(LexErrorKind::UnterminatedChar));
+            }
+        }
+
+        if !char.is_ascii() {
+            return Err(LexError::new(LexErrorKind::WideChar(char)));
+        }
+        Ok(Token::new(TokenType::Char, char.to_string(), l))
+    }
+    /// Returns the comment token type and the comment
+    /// Could also just return a slash or /= if the /  isn't followed by another slash or a star
+    ///
+    /// Block comments /* */ can be nested, every /* has to be closed by its own */.
+    ///
+    /// Comments starting with /// are doc comments, they document the function or constant
+    /// below them.
+    fn comment_token(&mut self, l: usize) -> Result<Token, LexError> {
+        let mut comment = String::new();
+        match self.peek() {
+            Some('/') => {
+                self.next();
+                // Exactly three slashes make a doc comment, //// is just a comment again
+                let token_type =
+                    if self.peek() == Some('/') && self.peak_nth(2) != Some(vec!['/', '/']) {
+                        self.next();
+                        TokenType::DocComment
+                    } else {
+                        TokenType::Comment
+                    };
                 for char in self.by_ref() {
                     match char {
-                        '\n' => return Token::new(TokenType::Comment, comment, l),
+                        '\n' => {
+                            // Leave the newline so the lexer still sees the end of the line
+                            self.advance_back(1);
+                            return Ok(Token::new(token_type, comment, l));
+                        }
                         _ => comment.push(char),
                     };
                 }
-                Token::new(TokenType::Comment, comment, l)
+                Ok(Token::new(token_type, comment, l))
+            }
+            Some('*') => {
+                self.next();
+                let mut depth = 1;
+                while let Some(char) = self.next() {
+                    match (char, self.peek()) {
+                        ('/', Some('*')) => {
+                            self.next();
+                            depth += 1;
+                            comment.push_str("/*");
+                        }
+                        ('*', Some('/')) => {
+                            self.next();
+                            depth -= 1;
+                            if depth == 0 {
+                                return Ok(Token::new(TokenType::Comment, comment, l));
+                            }
+                            comment.push_str("*/");
+                        }
+                        _ => comment.push(char),
+                    }
+                }
+                // If we get here the file ended before every block comment was closed
+                Err(LexError::new(LexErrorKind::UnterminatedComment))
             }
-            _ => Token::new(TokenType::Slash, "/", l),
+            _ => Ok(self.with_eq_token(l, (TokenType::Slash, "/"), (Operators::SlashEq, "/="))),
         }
     }
     /// Returns the token type and the value
-    fn number_token(&mut self, l: usize) -> Token {
+    ///
+    /// Numbers can be written as decimal 10, hex 0xA, binary 0b1010 or octal 0o12 and can be
+    /// seperated using underscores 1_000_000. The underscores are left out of the value, the
+    /// prefix is kept so the parser knows the radix.
+    fn number_token(&mut self, l: usize) -> Result<Token, LexError> {
         let mut number = String::new();
-        // We advance the cursor until we found a space which means there are no more numbers
-        // related to this number
 
         // This should techicly never fail
         let prev = self.previous().unwrap();
-        number.push(prev);
+        let radix = match (prev, self.peek()) {
+            ('0', Some('x' | 'X')) => 16,
+            ('0', Some('b' | 'B')) => 2,
+            ('0', Some('o' | 'O')) => 8,
+            _ => 10,
+        };
 
-        while let Some(char) = self.next() {
-            if char.is_numeric() {
-                number.push(char);
-                continue;
+        let mut has_digits = radix == 10;
+        if radix == 10 {
+            number.push(prev);
+        } else {
+            let prefix = self.next().unwrap();
+            number.push('0');
+            number.push(prefix.to_ascii_lowercase());
+        }
+
+        // We advance the cursor until we find something that can't be part of a number
+        while let Some(char) = self.peek() {
+            match char {
+                '_' => {
+                    self.next();
+                }
+                char if char.is_digit(radix) => {
+                    self.next();
+                    number.push(char);
+                    has_digits = true;
+                }
+                char if char.is_ascii_alphanumeric() => {
+                    // Consume the rest of the literal so it doesn't turn into other tokens
+                    while self
+                        .peek()
+                        .map_or(false, |char| char.is_ascii_alphanumeric() || char == '_')
+                    {
+                        self.next();
+                    }
+                    return Err(LexError::new(LexErrorKind::InvalidDigit {
+                        number,
+                        digit: char,
+                    }));
+                }
+                _ => break,
             }
-            self.advance_back(1);
-            return Token::new(TokenType::Number, number, l);
         }
-        // Return a invalid token if we did not find a space, this would mean the number is
-        // going on forever/ the file ended
-        Token::new(TokenType::Invalid, "Found a non ending number", l)
+
+        if !has_digits {
+            return Err(LexError::new(LexErrorKind::MissingDigits(number)));
+        }
+        Ok(Token::new(TokenType::Number, number, l))
     }
 
     // Matches the identifier to a keyword
@@ -234,28 +640,19 @@ impl Tokenizer for Cursor {
         let prev = self.previous().unwrap();
         identifier.push(prev);
 
-        while let Some(char) = self.next() {
-            match char {
-                ' ' => {
-                    if let Some(token) = Self::keyword_token(&identifier, l) {
-                        return token;
-                    }
-                    return Token::new(TokenType::Identifier, identifier, l);
-                }
-                '(' | ')' | '{' | '}' | '[' | ']' | '.' | ',' | '=' | '\n' | ';' => {
-                    self.advance_back(1);
-                    if let Some(token) = Self::keyword_token(&identifier, l) {
-                        return token;
-                    }
-                    return Token::new(TokenType::Identifier, identifier, l);
-                }
-                _ => identifier.push(char),
+        // Anything that can't continue a identifier ends it, so whitespace and every operator
+        while let Some(char) = self.peek() {
+            if !is_xid_continue(char) {
+                break;
             }
+            self.next();
+            identifier.push(char);
         }
 
-        // Return a invalid token if we did not find a space, this would mean the identifier is
-        // going on forever/the file ended
-        Token::new(TokenType::Invalid, "Found a non ending identifier", l)
+        if let Some(token) = Self::keyword_token(&identifier, l) {
+            return token;
+        }
+        Token::new(TokenType::Identifier, identifier, l)
     }
 
     // Returns Or token if it only finds one | other wise it returns OrOr token
@@ -279,6 +676,46 @@ impl Tokenizer for Cursor {
             }
         }
     }
+
+    // Returns DotDot token if it finds two dots other wise it returns Dot token
+    fn dot_token(&mut self, l: usize) -> Token {
+        match &self.next() {
+            Some('.') => Token::new(TokenType::DotDot, "..", l),
+            _ => {
+                self.advance_back(1);
+                Token::new(TokenType::Dot, ".", l)
+            }
+        }
+    }
+    /// Returns the tokens that can't fail to lex, these are mostly single char tokens and
+    /// identifiers. Any char that doesn't start a token is a error.
+    fn symbol_token(&mut self, char: char, l: usize) -> Result<Token, LexError> {
+        let token = match char {
+            ',' => Token::new(TokenType::Comma, ",", l),
+            '+' => self.with_eq_token(l, (TokenType::Plus, "+"), (Operators::PlusEq, "+=")),
+            '-' => self.with_eq_token(l, (TokenType::Min, "-"), (Operators::MinEq, "-=")),
+            '*' => self.with_eq_token(l, (TokenType::Star, "*"), (Operators::StarEq, "*=")),
+            '%' => self.with_eq_token(l, (TokenType::Percent, "%"), (Operators::PercentEq, "%=")),
+            '!' => self.with_eq_token(l, (TokenType::Not, "!"), (Operators::NotEq, "!=")),
+            '.' => self.dot_token(l),
+            '}' => Token::new(TokenType::CloseCurlyBracket, "}", l),
+            '{' => Token::new(TokenType::OpenCurlyBracket, "{", l),
+            '(' => Token::new(TokenType::OpenBrace, "(", l),
+            ')' => Token::new(TokenType::CloseBrace, ")", l),
+            ':' => Token::new(TokenType::Colon, ":", l),
+            '=' => self.eq_token(l),
+            '>' => self.more_token(l),
+            '<' => self.less_token(l),
+            '|' => self.or_token(l),
+            '&' => self.and_token(l),
+            '[' => Token::new(TokenType::OpenBracket, "[", l),
+            ']' => Token::new(TokenType::CloseBracket, "]", l),
+            ';' => Token::new(TokenType::SemiColon, ";", l),
+            char if is_identifier_start(char) => self.identifier_token(l),
+            _ => return Err(LexError::new(LexErrorKind::UnknownChar(char))),
+        };
+        Ok(token)
+    }
     /// Returns a token if the token is in the existing field of tokens
     fn keyword_token(t: impl AsRef<str>, l: usize) -> Option<Token> {
         match t.as_ref() {
@@ -288,6 +725,13 @@ impl Tokenizer for Cursor {
             "else" => Some(Token::new(TokenType::Keyword(KeyWords::Else), "else", l)),
             "while" => Some(Token::new(TokenType::Keyword(KeyWords::While), "while", l)),
             "for" => Some(Token::new(TokenType::Keyword(KeyWords::For), "for", l)),
+            "in" => Some(Token::new(TokenType::Keyword(KeyWords::In), "in", l)),
+            "break" => Some(Token::new(TokenType::Keyword(KeyWords::Break), "break", l)),
+            "continue" => Some(Token::new(
+                TokenType::Keyword(KeyWords::Continue),
+                "continue",
+                l,
+            )),
             "number" => Some(Token::new(
                 TokenType::Keyword(KeyWords::Number),
                 "number",
@@ -298,6 +742,7 @@ impl Tokenizer for Cursor {
                 "string",
                 l,
             )),
+            "char" => Some(Token::new(TokenType::Keyword(KeyWords::Char), "char", l)),
             "return" => Some(Token::new(
                 TokenType::Keyword(KeyWords::Return),
                 "return",
@@ -305,6 +750,11 @@ impl Tokenizer for Cursor {
             )),
             "use" => Some(Token::new(TokenType::Keyword(KeyWords::Use), "use", l)),
             "const" => Some(Token::new(TokenType::Keyword(KeyWords::Const), "const", l)),
+            "struct" => Some(Token::new(
+                TokenType::Keyword(KeyWords::Struct),
+                "struct",
+                l,
+            )),
             value if value == "true" || value == "false" => {
                 Some(Token::new(TokenType::Keyword(KeyWords::Bool), value, l))
             }
@@ -313,43 +763,247 @@ impl Tokenizer for Cursor {
     }
 }
 
-impl Lexer for Token {
-    fn lex(input: String) -> Vec<Token> {
-        let mut vec = Vec::new();
-        let mut cursor = Cursor::new(input);
-        let mut line = 1;
-        while let Some(token) = cursor.next() {
-            match token {
-                '\n' => {
-                    line += 1;
+/// A lazy stream of tokens, the tokens are only lexed when they are asked for.
+/// The stream borrows the input so nothing is copied up front.
+///
+/// # Example
+/// ```
+/// use lepa::parser_lexer::lexer::{KeyWords, Lexer, Token, TokenType};
+///
+/// let mut tokens = Token::stream("let x = 10;");
+/// assert_eq!(tokens.next().unwrap().token_type, TokenType::Keyword(KeyWords::Let));
+/// assert_eq!(tokens.next().unwrap().value, "x");
+/// ```
+#[derive(Clone)]
+pub struct Tokens<'a> {
+    cursor: Cursor<'a>,
+    errors: Vec<LexError>,
+    /// In lossless mode the whitespace and comments are kept as trivia instead of being skipped
+    lossless: bool,
+    trivia: Vec<Trivia<'a>>,
+    file: Option<FileId>,
+}
+
+impl<'a> Tokens<'a> {
+    pub fn new(input: &'a str) -> Tokens<'a> {
+        Tokens {
+            cursor: Cursor::new(input),
+            errors: Vec::new(),
+            lossless: false,
+            trivia: Vec::new(),
+            file: None,
+        }
+    }
+
+    /// Lex the input as the given file from the source map, every span will point to this file
+    pub fn in_file(mut self, file: FileId) -> Tokens<'a> {
+        self.file = Some(file);
+        self
+    }
+
+    /// The errors found in the tokens that have been lexed so far
+    pub fn errors(&self) -> &[LexError] {
+        &self.errors
+    }
+
+    /// Keeps the trivia found before the next token, whitespace right after whitespace is merged
+    /// into a single piece of trivia.
+    fn push_trivia(&mut self, kind: TriviaKind, span: Span) {
+        if let Some(last) = self.trivia.last_mut() {
+            if kind == TriviaKind::Whitespace && last.kind == kind && last.span.end == span.start {
+                last.span = last.span.to(&span);
+                last.text = self.cursor.slice(last.span.start, last.span.end);
+                return;
+            }
+        }
+        self.trivia.push(Trivia {
+            kind,
+            text: self.cursor.slice(span.start, span.end),
+            span,
+        });
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // The start of the token is taken before consuming the first char
+            let start = self.cursor.position();
+            let line = self.cursor.line();
+            let column = self.cursor.column();
+            let token = self.cursor.next()?;
+            let token = match token {
+                ' ' | '\t' | '\r' | '\n' => {
+                    if self.lossless {
+                        let kind = match token {
+                            '\n' => TriviaKind::Newline,
+                            _ => TriviaKind::Whitespace,
+                        };
+                        let span = Span::new(start, self.cursor.position(), line, column)
+                            .in_file(self.file);
+                        self.push_trivia(kind, span);
+                    }
                     continue;
                 }
-                ' ' | '\t' => continue,
-                ',' => vec.push(Token::new(TokenType::Comma, ",", line)),
-                '+' => vec.push(Token::new(TokenType::Plus, "+", line)),
-                '-' => vec.push(Token::new(TokenType::Min, "-", line)),
-                '/' => vec.push(cursor.comment_token(line)),
-                '.' => vec.push(Token::new(TokenType::Dot, ".", line)),
-                '}' => vec.push(Token::new(TokenType::CloseCurlyBracket, "}", line)),
-                '{' => vec.push(Token::new(TokenType::OpenCurlyBracket, "{", line)),
-                '(' => vec.push(Token::new(TokenType::OpenBrace, "(", line)),
-                ')' => vec.push(Token::new(TokenType::CloseBrace, ")", line)),
-                ':' => vec.push(Token::new(TokenType::Colon, ":", line)),
-                '=' => vec.push(cursor.eq_token(line)),
-                '>' => vec.push(cursor.more_token(line)),
-                '<' => vec.push(cursor.less_token(line)),
-                '0'..='9' => vec.push(cursor.number_token(line)),
-                '"' => vec.push(cursor.string_token(line)),
-                '|' => vec.push(cursor.or_token(line)),
-                //'\'' => vec.push(cursor.char_token(line)),
-                '&' => vec.push(cursor.and_token(line)),
-                '[' => vec.push(Token::new(TokenType::OpenBracket, "[", line)),
-                ']' => vec.push(Token::new(TokenType::CloseBracket, "]", line)),
-                ';' => vec.push(Token::new(TokenType::SemiColon, ";", line)),
-                'A'..='Z' | 'a'..='z' | '@' => vec.push(cursor.identifier_token(line)),
-                _ => vec.push(Token::new(TokenType::Invalid, "Invalid token", line)),
-            }
+                '/' => self.cursor.comment_token(line),
+                '0'..='9' => self.cursor.number_token(line),
+                '"' => self.cursor.string_token(line),
+                'r' if matches!(self.cursor.peek(), Some('"') | Some('#')) => {
+                    self.cursor.raw_string_token(line)
+                }
+                '\'' => self.cursor.char_token(line),
+                char => self.cursor.symbol_token(char, line),
+            };
+            let span = Span::new(start, self.cursor.position(), line, column).in_file(self.file);
+            return match token {
+                Ok(token) if self.lossless && token.token_type == TokenType::Comment => {
+                    self.push_trivia(TriviaKind::Comment, span);
+                    continue;
+                }
+                Ok(token) => Some(token.with_span(span)),
+                Err(error) => {
+                    // The invalid token keeps the source it was lexed from, the message lives
+                    // in the error. A error that already points at a part of the token, such as
+                    // a invalid escape, keeps its own span.
+                    let error_span = match error.span {
+                        error_span if error_span == Span::default() => span,
+                        error_span => error_span.in_file(self.file),
+                    };
+                    self.errors.push(error.with_span(error_span));
+                    let value = self.cursor.slice(start, span.end);
+                    Some(Token::new(TokenType::Invalid, value, line).with_span(span))
+                }
+            };
         }
-        vec
+    }
+}
+
+/// The kind of source that doesn't change the meaning of the program
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TriviaKind {
+    /// Spaces, tabs and carriage returns
+    Whitespace,
+    Newline,
+    /// Both // and /* */ comments, doc comments are tokens because they document the code
+    Comment,
+}
+
+/// A piece of the source the lexer would normally skip
+#[derive(Debug, PartialEq, Clone)]
+pub struct Trivia<'a> {
+    pub kind: TriviaKind,
+    pub text: &'a str,
+    pub span: Span,
+}
+
+/// A token together with the trivia in front of it and the exact text it was lexed from.
+///
+/// The value of a token can differ from the source, for example a string has its escapes
+/// decoded, the text is always the source as written.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LosslessToken<'a> {
+    pub leading_trivia: Vec<Trivia<'a>>,
+    pub token: Token,
+    pub text: &'a str,
+}
+
+impl<'a> Display for LosslessToken<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for trivia in &self.leading_trivia {
+            write!(f, "{}", trivia.text)?;
+        }
+        write!(f, "{}", self.text)
+    }
+}
+
+/// A stream of tokens that keeps every byte of the input, used by tooling such as formatters.
+///
+/// Writing every token followed by the trailing trivia gives back the input byte for byte.
+///
+/// # Example
+/// ```
+/// use lepa::parser_lexer::lexer::{Lexer, Token};
+///
+/// let input = "let x = 10; // ten\n";
+/// let mut lossless = Token::lossless(input);
+/// let mut source: String = lossless.by_ref().map(|token| token.to_string()).collect();
+/// for trivia in lossless.trailing_trivia() {
+///     source.push_str(trivia.text);
+/// }
+/// assert_eq!(source, input);
+/// ```
+#[derive(Clone)]
+pub struct Lossless<'a> {
+    tokens: Tokens<'a>,
+    trailing_trivia: Vec<Trivia<'a>>,
+}
+
+impl<'a> Lossless<'a> {
+    pub fn new(input: &'a str) -> Lossless<'a> {
+        let mut tokens = Tokens::new(input);
+        tokens.lossless = true;
+        Lossless {
+            tokens,
+            trailing_trivia: Vec::new(),
+        }
+    }
+
+    /// The trivia after the last token, this is only known once every token has been lexed
+    pub fn trailing_trivia(&self) -> &[Trivia<'a>] {
+        &self.trailing_trivia
+    }
+
+    /// The errors found in the tokens that have been lexed so far
+    pub fn errors(&self) -> &[LexError] {
+        self.tokens.errors()
+    }
+}
+
+impl<'a> Iterator for Lossless<'a> {
+    type Item = LosslessToken<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Some(token) = self.tokens.next() else {
+            self.trailing_trivia.append(&mut self.tokens.trivia);
+            return None;
+        };
+        Some(LosslessToken {
+            leading_trivia: std::mem::take(&mut self.tokens.trivia),
+            text: self.tokens.cursor.slice(token.span.start, token.span.end),
+            token,
+        })
+    }
+}
+
+impl Lexer for Token {
+    /// Lexes the whole input at once, this is a thin wrapper around [`Token::stream`].
+    fn lex(input: String) -> Vec<Token> {
+        Tokens::new(&input).collect()
+    }
+
+    /// Lexes the whole input and returns the errors that were found next to the tokens.
+    fn lex_with_errors(input: String) -> (Vec<Token>, Vec<LexError>) {
+        let mut stream = Tokens::new(&input);
+        let tokens = stream.by_ref().collect();
+        (tokens, stream.errors)
+    }
+
+    /// Loads the file into the source map and lexes it, errors found in these tokens will know
+    /// which file they came from.
+    fn lex_file(name: &str, input: String) -> (Vec<Token>, Vec<LexError>) {
+        let file = load_file(name, &input);
+        let mut stream = Tokens::new(&input).in_file(file);
+        let tokens = stream.by_ref().collect();
+        (tokens, stream.errors)
+    }
+
+    fn stream(input: &str) -> Tokens<'_> {
+        Tokens::new(input)
+    }
+
+    fn lossless(input: &str) -> Lossless<'_> {
+        Lossless::new(input)
     }
 }
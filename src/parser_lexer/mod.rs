@@ -1,2 +1,4 @@
 pub mod lexer;
 pub mod parser;
+pub mod source_map;
+pub mod symbol;
@@ -1,22 +1,34 @@
+use crate::ast::expr::{BinOp, Expr, ExprKind, UnaryOp};
 use crate::ast::variable::TypeVar;
 use crate::ast::variable::Variable;
-use crate::errors::error_messages::args_no_close;
+use crate::ast::variable::{Assign, AssignTarget};
+use crate::errors::error_messages::{
+    args_no_close, duplicate_field, expression_no_close, invalid_expression, invalid_field_access,
+    invalid_struct, invalid_struct_literal, lex_error, missing_expression, number_out_of_range,
+};
 use crate::{
     ast::{
-        ast::{Arg, Ast, Case, Logic, Return, ReturnTypes, Type, TypesArg},
+        ast::{Arg, Ast, Condition, For, ForIter, Logic, Return, ReturnTypes, Type, TypesArg, While},
         function::Func,
+        struct_::{Field, Struct},
         use_::Use,
         variable::VarBuilder,
     },
     errors::{
-        error::{BuildError, ErrorBuilder},
+        error::ErrorBuilder,
         error_messages::{
-            invalid_arr_no_end, invalid_function_body_syntax, invalid_function_call,
-            invalid_function_syntax_missing_id, invalid_if_statement_body, invalid_return_no_end,
-            invalid_use, invalid_var_syntax_token, non_ending_variable,
+            invalid_argument_type, invalid_arr_no_end, invalid_assignment, invalid_function_body_syntax, invalid_function_call,
+            invalid_for_loop, invalid_function_syntax_missing_id, invalid_if_statement_body,
+            invalid_if_statement_operator, invalid_return_no_end,
+            invalid_token, invalid_type_annotation, invalid_use, invalid_var_syntax_token,
+            invalid_while_body, mismatched_type_annotation, missing_type_annotation,
+            missing_value_annotation, non_ending_statement, non_ending_variable, unexpected_token,
         },
     },
-    parser_lexer::lexer::{KeyWords, Operators, Token, TokenType},
+    parser_lexer::{
+        lexer::{KeyWords, LexError, Operators, Span, Token, TokenType},
+        symbol::Symbol,
+    },
 };
 
 /// Parser struct
@@ -28,6 +40,11 @@ pub struct Parser {
     pub current_position: usize,
     pub tokens: Vec<Token>,
     pub prev_token: Option<Token>,
+    pub lex_errors: Vec<LexError>,
+    /// Doc comments waiting for the function or constant they document
+    pub docs: Vec<String>,
+    /// Errors found so far, the parser skips past them to find the errors after them
+    pub errors: Vec<ErrorBuilder>,
 }
 
 impl Parser {
@@ -37,6 +54,144 @@ impl Parser {
             current_position: 0,
             tokens,
             prev_token: None,
+            lex_errors: Vec::new(),
+            docs: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Pass along the errors the lexer found, the parser won't parse the tokens if there are any.
+    pub fn with_lex_errors(mut self, lex_errors: Vec<LexError>) -> Parser {
+        self.lex_errors = lex_errors;
+        self
+    }
+
+    /// Comments can be written in between the parts of a statement, they don't mean anything
+    /// there so they get skipped.
+    fn skip_comments(&mut self) {
+        while let Some(token) = self.tokens.get(self.current_position) {
+            match token.token_type {
+                TokenType::Comment | TokenType::DocComment => {
+                    self.next();
+                }
+                _ => return,
+            }
+        }
+    }
+
+    /// Parses the type written after the colon of a annotation: number, string, char or the name
+    /// of a struct
+    fn parse_annotation(&mut self) -> Result<TypesArg, ErrorBuilder> {
+        let prev = self.prev_token.clone().unwrap();
+        let Some(token) = self.next() else {
+            return Err(invalid_type_annotation(None, prev.span));
+        };
+        match token.token_type {
+            TokenType::Keyword(KeyWords::Number) => Ok(TypesArg::Number),
+            TokenType::Keyword(KeyWords::String) => Ok(TypesArg::String),
+            TokenType::Keyword(KeyWords::Char) => Ok(TypesArg::Char),
+            TokenType::Identifier => Ok(TypesArg::Struct(token.value)),
+            _ => Err(invalid_type_annotation(Some(token.value), token.span)),
+        }
+    }
+
+    /// Skips the rest of a statement that failed to parse so the statements after it still get
+    /// parsed.
+    ///
+    /// Parsing continues after the next `;` or before the next `}` or top level keyword. `start`
+    /// is the position right after the first token of the statement.
+    ///
+    /// A block is skipped as a whole, so the body of a function or if statement with a broken
+    /// header doesn't end up in the ast: fn f(1 2) { let x = 1; }
+    fn recover(&mut self, start: usize) {
+        if self.current_position > start {
+            match self.prev_token.as_ref().map(|token| &token.token_type) {
+                // The statement failed on the semicolon that ends it
+                Some(TokenType::SemiColon) => return,
+                // The bracket closes the block around the statement
+                Some(TokenType::CloseCurlyBracket) => {
+                    self.advance_back(1);
+                    return;
+                }
+                _ => {}
+            }
+        }
+        while let Some(token) = self.peak_nth(0) {
+            match token.token_type {
+                TokenType::SemiColon => {
+                    self.next();
+                    return;
+                }
+                // The block belongs to the statement, the statement ends with it
+                TokenType::OpenCurlyBracket => {
+                    self.skip_block();
+                    if self
+                        .peak_nth(0)
+                        .is_some_and(|token| token.token_type == TokenType::SemiColon)
+                    {
+                        self.next();
+                    }
+                    return;
+                }
+                TokenType::CloseCurlyBracket
+                | TokenType::Keyword(
+                    KeyWords::Fn
+                    | KeyWords::Let
+                    | KeyWords::Const
+                    | KeyWords::Use
+                    | KeyWords::Struct,
+                ) => return,
+                _ => {
+                    self.next();
+                }
+            }
+        }
+    }
+
+    /// Skips the block starting at the next token, including every block nested within it
+    fn skip_block(&mut self) {
+        let mut depth = 0;
+        for token in self.by_ref() {
+            match token.token_type {
+                TokenType::OpenCurlyBracket => depth += 1,
+                TokenType::CloseCurlyBracket => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Skips the rest of a struct declaration that failed to parse, up until and including its
+    /// closing bracket.
+    fn recover_struct(&mut self) {
+        if self
+            .prev_token
+            .as_ref()
+            .is_some_and(|token| token.token_type == TokenType::CloseCurlyBracket)
+        {
+            return;
+        }
+        while let Some(token) = self.peak_nth(0) {
+            match token.token_type {
+                TokenType::CloseCurlyBracket => {
+                    self.next();
+                    return;
+                }
+                TokenType::Keyword(
+                    KeyWords::Fn
+                    | KeyWords::Let
+                    | KeyWords::Const
+                    | KeyWords::Use
+                    | KeyWords::Struct,
+                ) => return,
+                _ => {
+                    self.next();
+                }
+            }
         }
     }
 }
@@ -79,17 +234,20 @@ pub trait WalkParser {
     /// # Example
     ///
     /// ```rust
-    ///  use lepa::parser_lexer::lexer::lexer::{Token, Lexer, TokenType};
+    ///  use lepa::parser_lexer::lexer::{Token, Lexer, TokenType};
     ///  use lepa::parser_lexer::parser::Parser;
     ///  use lepa::parser_lexer::parser::WalkParser;
     ///
     ///  let mut parser = Parser {
     ///     current_position:0,
-    ///     tokens: vec![Token { value: "".into(), token_type:TokenType::OpenBrace, line:0 }, Token { value: "".into(), token_type:TokenType::OpenBrace, line:0 }, Token { value: "".into(), token_type:TokenType::OpenBrace, line:0 }],
-    ///     prev_token:None
+    ///     tokens: vec![Token::new(TokenType::OpenBrace, "", 0), Token::new(TokenType::OpenBrace, "", 0), Token::new(TokenType::OpenBrace, "", 0)],
+    ///     prev_token:None,
+    ///     lex_errors: Vec::new(),
+    ///     docs: Vec::new(),
+    ///     errors: Vec::new(),
     ///  };
     ///  let token:Option<Token> = parser.peak_nth(2);
-    ///  assert_eq!(token, Some(Token { value:"".into(),  token_type:TokenType::OpenBrace, line:0}));
+    ///  assert_eq!(token, Some(Token::new(TokenType::OpenBrace, "", 0)));
     /// ```
     ///
     /// **This wont advance the current_position therefore not "consuming" the tokens**
@@ -100,7 +258,7 @@ pub trait WalkParser {
     /// # Example
     ///
     /// ``` rust
-    ///  use lepa::parser_lexer::lexer::lexer::{Token, Lexer, TokenType, KeyWords};
+    ///  use lepa::parser_lexer::lexer::{Token, Lexer, TokenType, KeyWords};
     ///  use lepa::parser_lexer::parser::Parser;
     ///  use lepa::parser_lexer::parser::WalkParser;
     ///
@@ -108,7 +266,10 @@ pub trait WalkParser {
     ///  let mut parser:Parser = Parser {
     ///  current_position:0,
     ///  tokens:parser,
-    ///  prev_token:None
+    ///  prev_token:None,
+    ///  lex_errors: Vec::new(),
+    ///  docs: Vec::new(),
+    ///  errors: Vec::new(),
     ///  };
     ///  let token:Option<Vec<Token>> = parser.peak_nth_all(2);
     /// ```
@@ -122,7 +283,7 @@ pub trait WalkParser {
     /// # Example
     ///
     /// ``` rust
-    ///  use lepa::parser_lexer::lexer::lexer::{Token, Lexer, TokenType, KeyWords};
+    ///  use lepa::parser_lexer::lexer::{Token, Lexer, TokenType, KeyWords};
     ///  use lepa::parser_lexer::parser::Parser;
     ///  use lepa::parser_lexer::parser::WalkParser;
     ///
@@ -130,7 +291,10 @@ pub trait WalkParser {
     ///  let mut parser:Parser = Parser {
     ///  current_position:0,
     ///  tokens:parser,
-    ///  prev_token:None
+    ///  prev_token:None,
+    ///  lex_errors: Vec::new(),
+    ///  docs: Vec::new(),
+    ///  errors: Vec::new(),
     ///  };
     ///  let token:Option<Vec<Token>> = parser.up_until_token(TokenType::OpenBrace);
     /// ```
@@ -164,82 +328,301 @@ pub trait WalkParser {
 }
 
 pub trait CaseLogic {
-    fn get_case(&mut self) -> Result<Vec<Case>, ErrorBuilder>;
+    /// Parses the condition of a if statement, the condition can be any expression.
+    ///
+    /// Comparisons, && || and ! make up the [`Condition`] tree, any other value is used as a
+    /// boolean.
+    fn get_case(&mut self) -> Result<Condition, ErrorBuilder>;
 }
 
 impl CaseLogic for Parser {
-    fn get_case(&mut self) -> Result<Vec<Case>, ErrorBuilder> {
-        let type_var_1: TypeVar;
-        let type_var_2: TypeVar;
-        let current_case: Case;
-        let mut case = vec![];
-
-        let val_1 = self.next().unwrap();
-        match val_1.token_type {
-            TokenType::Number => {
-                type_var_1 =  TypeVar::parse_number(val_1.value);
-            }
-            TokenType::Identifier => {
-                type_var_1 =  TypeVar::Identifier(val_1.value);
+    fn get_case(&mut self) -> Result<Condition, ErrorBuilder> {
+        let condition = self.parse_expr()?;
+
+        // Assignments can't be used to compare values: if a = 1 {}
+        self.skip_comments();
+        if let Some(token) = self.peak_nth(0) {
+            if let TokenType::Operator(op) = token.token_type {
+                return Err(invalid_if_statement_operator(op, token.span));
             }
-            TokenType::String => {
-                type_var_1 =  TypeVar::String(val_1.value);
+        }
+        Ok(Condition::from_expr(condition))
+    }
+}
+
+/// Parsing expressions
+///
+/// Expressions are parsed using [precedence climbing](https://en.wikipedia.org/wiki/Operator-precedence_parser#Precedence_climbing_method),
+/// the precedence of every operator can be found at [`BinOp::precedence`].
+///
+/// # Example
+///
+/// 1 + 2 * 3 -> 1 + (2 * 3)
+///
+/// a - b - c -> (a - b) - c
+///
+/// (a + b) * -c
+pub trait ParseExpr {
+    /// Parses a full expression, including comparisons and && ||
+    fn parse_expr(&mut self) -> Result<Expr, ErrorBuilder>;
+    /// Parses a expression that only contains operators with at least the given precedence
+    fn parse_binary(&mut self, min_precedence: u8) -> Result<Expr, ErrorBuilder>;
+    /// Parses the operators written in front of a value: -a, !a
+    fn parse_unary(&mut self) -> Result<Expr, ErrorBuilder>;
+    /// Parses a single value: literals, identifiers, calls, arrays, indexes, struct literals,
+    /// field accesses and parenthesised expressions
+    fn parse_primary(&mut self) -> Result<Expr, ErrorBuilder>;
+    /// Parses the indexes and field accesses written after a value, they are applied from left
+    /// to right:
+    ///
+    /// points[0].x -> Field(Index(points, 0), x)
+    fn parse_postfix(&mut self, value: Expr) -> Result<Expr, ErrorBuilder>;
+    /// Parses a new value of a struct, expects the name of the struct to be the previous token
+    /// and the opening curly bracket to be the next token:
+    ///
+    /// Point { x: 1, y: a + 1 }
+    fn parse_struct_literal(&mut self) -> Result<Expr, ErrorBuilder>;
+    /// Parses the index of a array, the opening bracket is the next token:
+    ///
+    /// arr[i + 1]
+    fn parse_index(&mut self) -> Result<Expr, ErrorBuilder>;
+    /// Parses a call to a function, expects the name of the function to be the previous token.
+    ///
+    /// Unlike [`ParseTokens::parse_fn_call`] it doesn't expect a semicolon after the call.
+    fn parse_call(&mut self) -> Result<Func, ErrorBuilder>;
+    /// Parses the values passed to a function call:
+    ///
+    /// # Example
+    ///
+    /// some(a, b + 1, other(c))
+    fn parse_call_args(&mut self) -> Result<Vec<Arg>, ErrorBuilder>;
+}
+
+impl ParseExpr for Parser {
+    fn parse_expr(&mut self) -> Result<Expr, ErrorBuilder> {
+        self.parse_binary(0)
+    }
+    fn parse_binary(&mut self, min_precedence: u8) -> Result<Expr, ErrorBuilder> {
+        let mut lhs = self.parse_unary()?;
+        loop {
+            self.skip_comments();
+            let Some(op) = self.peak_nth(0).and_then(|token| BinOp::from_token(&token.token_type)) else {
+                return Ok(lhs);
+            };
+            if op.precedence() < min_precedence {
+                return Ok(lhs);
             }
-            _ => return Err(ErrorBuilder::new()
-                .message("The first value of this if statement is either not supported yet or incorrect.")
-                .line(val_1.line)
-                .file_name("todo")
-                .build_error()),
+            self.next();
+            // Only operators that bind stronger may be part of the right side, this makes
+            // operators with the same precedence left associative.
+            let rhs = self.parse_binary(op.precedence() + 1)?;
+            lhs = Expr::binary(op, lhs, rhs);
+        }
+    }
+    fn parse_unary(&mut self) -> Result<Expr, ErrorBuilder> {
+        self.skip_comments();
+        let Some(token) = self.peak_nth(0) else {
+            return self.parse_primary();
+        };
+        let op = match token.token_type {
+            TokenType::Min => UnaryOp::Neg,
+            TokenType::Not => UnaryOp::Not,
+            _ => return self.parse_primary(),
         };
+        self.next();
 
-        let case_token = self.next().unwrap();
-        match case_token.token_type {
-            TokenType::Operator(op) => {
-                current_case = Case::from_op(op)?;
-            }
-            _ => {
-                return Err(ErrorBuilder::new()
-                    .message("Pleas replace your current case with a valid if case")
-                    .line(val_1.line)
-                    .file_name("todo")
-                    .build_error())
+        // Negative number literals are kept as a number, so they can still be used everywhere a
+        // number can be used.
+        if op == UnaryOp::Neg {
+            if let Some(number) = self
+                .peak_nth(0)
+                .filter(|number| number.token_type == TokenType::Number)
+            {
+                self.next();
+                let span = token.span.to(&number.span);
+                let Some(num) = TypeVar::parse_int(&format!("-{}", number.value)) else {
+                    return Err(number_out_of_range(number.value, span));
+                };
+                return Ok(Expr::new(ExprKind::Number(num), span));
             }
+        }
+
+        let expr = self.parse_unary()?;
+        let span = token.span.to(&expr.span);
+        Ok(Expr::unary(op, expr, span))
+    }
+    fn parse_primary(&mut self) -> Result<Expr, ErrorBuilder> {
+        self.skip_comments();
+        let Some(token) = self.next() else {
+            let span = self.prev_token.as_ref().map_or(Span::default(), |token| token.span);
+            return Err(missing_expression(span));
         };
 
-        let val_2 = self.next().unwrap();
-        match val_2.token_type {
+        let kind = match token.token_type {
             TokenType::Number => {
-                type_var_2 =  TypeVar::parse_number(val_2.value);
+                let Some(num) = TypeVar::parse_int(token.value.as_str()) else {
+                    return Err(number_out_of_range(token.value, token.span));
+                };
+                ExprKind::Number(num)
             }
+            TokenType::String => ExprKind::String(token.value.to_string()),
+            TokenType::Char => ExprKind::Char(token.value.as_str().chars().next().unwrap_or('\0')),
+            TokenType::Keyword(KeyWords::Bool) => ExprKind::Bool(token.value == "true"),
             TokenType::Identifier => {
-                type_var_2 =  TypeVar::Identifier(val_2.value);
+                let value = match self.peak_nth(0).map(|next| next.token_type) {
+                    Some(TokenType::OpenBrace) => {
+                        let call = self.parse_call()?;
+                        Expr::new(ExprKind::Call(call.clone()), call.span)
+                    }
+                    // A block after a identifier only is a struct literal if it starts with a
+                    // field, otherwise `if a { ... }` would be read as one.
+                    Some(TokenType::OpenCurlyBracket)
+                        if self.peak_nth(1).map(|field| field.token_type)
+                            == Some(TokenType::Identifier)
+                            && self.peak_nth(2).map(|colon| colon.token_type)
+                                == Some(TokenType::Colon) =>
+                    {
+                        self.parse_struct_literal()?
+                    }
+                    _ => Expr::new(ExprKind::Identifier(token.value), token.span),
+                };
+                return self.parse_postfix(value);
             }
-            TokenType::String => {
-                type_var_2 =  TypeVar::String(val_2.value);
+            TokenType::OpenBrace => {
+                let expr = self.parse_expr()?;
+                self.skip_comments();
+                return match self.next() {
+                    Some(close) if close.token_type == TokenType::CloseBrace => Ok(expr),
+                    _ => Err(expression_no_close(token.span)),
+                };
             }
-            _ => return Err(ErrorBuilder::new()
-                .message("The first value of this if statement is either not supported yet or incorrect.")
-                .line(val_2.line)
-                .file_name("todo")
-                .build_error()),
+            TokenType::OpenBracket => ExprKind::Array(self.parse_array()?),
+            _ => return Err(invalid_expression(token)),
         };
 
-        match self.next() {
-            Some(val) => {
-                let val = val.token_type;
-                match val {
-                    TokenType::AndAnd | TokenType::OrOr => {
-                        case.append(&mut self.get_case()?);
+        // The value reaches up until the last token we consumed
+        let end = self.prev_token.as_ref().map_or(token.span, |end| end.span);
+        Ok(Expr::new(kind, token.span.to(&end)))
+    }
+    fn parse_postfix(&mut self, mut value: Expr) -> Result<Expr, ErrorBuilder> {
+        loop {
+            let start = value.span;
+            let kind = match self.peak_nth(0).map(|next| next.token_type) {
+                Some(TokenType::OpenBracket) => ExprKind::Index {
+                    index: Box::new(self.parse_index()?),
+                    array: Box::new(value),
+                },
+                Some(TokenType::Dot) => {
+                    let dot = self.next().unwrap();
+                    let Some(field) = self
+                        .next()
+                        .filter(|field| field.token_type == TokenType::Identifier)
+                    else {
+                        return Err(invalid_field_access(dot.span));
+                    };
+                    ExprKind::Field {
+                        value: Box::new(value),
+                        field: field.value,
                     }
-                    _ => self.advance_back(1),
                 }
+                _ => return Ok(value),
+            };
+            let end = self.prev_token.as_ref().map_or(start, |end| end.span);
+            value = Expr::new(kind, start.to(&end));
+        }
+    }
+    fn parse_struct_literal(&mut self) -> Result<Expr, ErrorBuilder> {
+        let name = self.prev_token.clone().unwrap();
+        self.next();
+        let mut fields: Vec<(Symbol, Expr)> = Vec::new();
+
+        loop {
+            self.skip_comments();
+            // Also allows a trailing comma: Point { x: 1, y: 2, }
+            let field = match self.next() {
+                Some(close)
+                    if close.token_type == TokenType::CloseCurlyBracket && !fields.is_empty() =>
+                {
+                    let kind = ExprKind::StructLit {
+                        name: name.value,
+                        fields,
+                    };
+                    return Ok(Expr::new(kind, name.span.to(&close.span)));
+                }
+                Some(field) if field.token_type == TokenType::Identifier => field,
+                _ => return Err(invalid_struct_literal(name.value, name.span)),
+            };
+            if fields.iter().any(|(written, _)| *written == field.value) {
+                return Err(duplicate_field(name.value, field.value, field.span));
+            }
+            self.skip_comments();
+            match self.next() {
+                Some(colon) if colon.token_type == TokenType::Colon => {}
+                _ => return Err(invalid_struct_literal(name.value, field.span)),
             }
-            _ => self.advance_back(1),
+            fields.push((field.value, self.parse_expr()?));
+
+            self.skip_comments();
+            match self.peak_nth(0) {
+                Some(comma) if comma.token_type == TokenType::Comma => {
+                    self.next();
+                }
+                Some(close) if close.token_type == TokenType::CloseCurlyBracket => {}
+                _ => return Err(invalid_struct_literal(name.value, name.span)),
+            }
+        }
+    }
+    fn parse_index(&mut self) -> Result<Expr, ErrorBuilder> {
+        let prev = self.prev_token.clone().unwrap();
+        self.next();
+        let index = self.parse_expr()?;
+        self.skip_comments();
+        match self.next() {
+            Some(close) if close.token_type == TokenType::CloseBracket => Ok(index),
+            _ => Err(invalid_arr_no_end(prev.span)),
         }
+    }
+    fn parse_call(&mut self) -> Result<Func, ErrorBuilder> {
+        let prev = self.prev_token.clone().unwrap();
+        let args = self.parse_call_args()?;
+        let end = self.prev_token.as_ref().map_or(prev.span, |token| token.span);
 
-        let assign = current_case.assign(type_var_1, type_var_2);
-        case.push(assign);
-        Ok(case)
+        Ok(Func {
+            name: prev.value,
+            args,
+            body: None,
+            return_type: ReturnTypes::None,
+            line: prev.line,
+            span: prev.span.to(&end),
+            docs: Vec::new(),
+        })
+    }
+    fn parse_call_args(&mut self) -> Result<Vec<Arg>, ErrorBuilder> {
+        let prev = self.prev_token.clone().unwrap();
+        let mut args = Vec::new();
+
+        match self.next() {
+            Some(open) if open.token_type == TokenType::OpenBrace => {}
+            _ => return Err(invalid_function_call(prev.value, prev.span)),
+        }
+        self.skip_comments();
+        if let Some(close) = self.peak_nth(0) {
+            if close.token_type == TokenType::CloseBrace {
+                self.next();
+                return Ok(args);
+            }
+        }
+
+        loop {
+            args.push(Arg::from_expr(self.parse_expr()?));
+            self.skip_comments();
+            match self.next() {
+                Some(token) if token.token_type == TokenType::Comma => continue,
+                Some(token) if token.token_type == TokenType::CloseBrace => return Ok(args),
+                Some(token) => return Err(invalid_var_syntax_token(token)),
+                None => return Err(args_no_close(prev.span)),
+            }
+        }
     }
 }
 
@@ -275,8 +658,14 @@ impl WalkParser for Parser {
 pub trait Parse {
     /// The main parsing function.
     ///
-    /// It converts the tokens into a ast
-    fn parse(&mut self) -> Result<Ast, ErrorBuilder>;
+    /// It converts the tokens into a ast, if anything went wrong every error is returned instead.
+    /// Displaying the errors is left to the caller.
+    fn parse(&mut self) -> Result<Ast, Vec<ErrorBuilder>>;
+    /// Parses the tokens without stopping at the first error
+    ///
+    /// A statement containing a error is left out of the ast and parsing continues after it, the
+    /// ast of everything else is returned together with every error that was found.
+    fn parse_all(&mut self) -> (Ast, Vec<ErrorBuilder>);
 }
 
 trait ParseTokens {
@@ -291,10 +680,12 @@ trait ParseTokens {
     ///
     /// - some = "wow";
     /// - some = 1;
+    /// - some = other * (2 + 1);
+    /// - some: number = other;
+    /// - some: char;
     ///
-    /// It doesn't support:
-    ///
-    /// - some = some;
+    /// A type written after the name is checked against literal values, a variable without a
+    /// value needs a type because there is nothing to infer it from.
     fn parse_var(&mut self) -> Result<Variable, ErrorBuilder>;
     /// Parsing blocks
     ///
@@ -325,6 +716,16 @@ trait ParseTokens {
     /// let wowo
     /// }
     fn parse_fn(&mut self) -> Result<Ast, ErrorBuilder>;
+    /// Parsing struct declarations, every field needs a type and the struct needs at least one
+    /// field
+    ///
+    /// # Example
+    ///
+    /// struct Point {
+    ///  x: number,
+    ///  y: number,
+    /// }
+    fn parse_struct(&mut self) -> Result<Struct, ErrorBuilder>;
     /// Parsing arguments
     ///
     /// This could be anything inbetween a OpenBrace and CloseBrace:
@@ -333,11 +734,14 @@ trait ParseTokens {
     ///
     /// fn some ( arg1, arg2, arg3 )
     ///
+    /// fn some ( number arg1, Point arg2 )
+    ///
     /// some( arg1, arg2, arg3 )
     ///
     /// ( arg1, arg2, arg3 )
     fn parse_args(&mut self) -> Result<Vec<Arg>, ErrorBuilder>;
-    /// Parsing arrays
+    /// Parsing arrays, expects the opening bracket to already be consumed and returns the values
+    /// of the array.
     ///
     /// # Examples
     ///
@@ -345,8 +749,10 @@ trait ParseTokens {
     /// [1,2,3,4,5];
     ///
     /// ["wow", "hello world", "googbye world"];
+    ///
+    /// [1 + 1, 2 * 2];
     /// ```
-    fn parse_array(&mut self) -> Result<TypeVar, ErrorBuilder>;
+    fn parse_array(&mut self) -> Result<Vec<Expr>, ErrorBuilder>;
     /// Parsing function calls:
     ///
     /// # Examples
@@ -359,6 +765,18 @@ trait ParseTokens {
     /// hello_world() // this would be a function call
     /// ```
     fn parse_fn_call(&mut self) -> Result<Func, ErrorBuilder>;
+    /// Parsing assignments to existing variables, expects the name to be the previous token
+    ///
+    /// # Example
+    ///
+    /// a = a + 1;
+    ///
+    /// a += 1;
+    ///
+    /// arr[i] = 10;
+    ///
+    /// p.x += 1;
+    fn parse_assign(&mut self) -> Result<Assign, ErrorBuilder>;
     /// Parsing returns:
     ///
     /// # Example
@@ -366,6 +784,8 @@ trait ParseTokens {
     /// fn goodbye_world(string number) number {
     ///  return number; // It parses this part.
     /// }
+    ///
+    /// Any expression can be returned: return add(a, 1) * 2; or nothing at all: return;
     fn parse_return(&mut self) -> Result<Return, ErrorBuilder>;
     fn parse_use(&mut self) -> Result<Use, ErrorBuilder>;
     // Parsing statements
@@ -384,43 +804,94 @@ trait ParseTokens {
     // }
     // ```
     fn parse_statement(&mut self) -> Result<Logic, ErrorBuilder>;
+    /// Parsing while loops, the condition is parsed like the condition of a if statement
+    ///
+    /// # Example
+    ///
+    /// while i < 10 {
+    ///  @printf("%d", i);
+    /// }
+    fn parse_while(&mut self) -> Result<While, ErrorBuilder>;
+    /// Parsing for loops over a range or a array
+    ///
+    /// # Example
+    ///
+    /// for i in 0..10 {
+    ///  @printf("%d", i);
+    /// }
+    ///
+    /// for x in [1, 2, 3] {
+    ///  @printf("%d", x);
+    /// }
+    fn parse_for(&mut self) -> Result<For, ErrorBuilder>;
+    /// Parsing break and continue, both of them only consist of the keyword and a semicolon.
+    fn parse_loop_control(&mut self) -> Result<Type, ErrorBuilder>;
 }
 
 impl Parse for Parser {
-    fn parse(&mut self) -> Result<Ast, ErrorBuilder> {
+    fn parse(&mut self) -> Result<Ast, Vec<ErrorBuilder>> {
+        let (ast, errors) = self.parse_all();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(ast)
+    }
+    fn parse_all(&mut self) -> (Ast, Vec<ErrorBuilder>) {
         let mut ast = Ast::new(Type::Program);
+        // The tokens can't be trusted if the lexer failed, so every lexer error gets reported
+        // instead of the parser running into the invalid tokens.
+        if !self.lex_errors.is_empty() {
+            return (ast, self.lex_errors.iter().map(lex_error).collect());
+        }
+
         while let Some(token) = self.next() {
-            match token.token_type {
+            let start = self.current_position;
+            let is_struct = token.token_type == TokenType::Keyword(KeyWords::Struct);
+            let item = match token.token_type {
                 // Parsing variables starting with let
                 TokenType::Keyword(KeyWords::Use) => {
-                    let use_ = Ast::new(Type::Use(self.parse_use()?));
-                    ast.body.push(use_);
+                    self.parse_use().map(|use_| Ast::new(Type::Use(use_)))
                 }
                 TokenType::Keyword(KeyWords::Const) => {
-                    let var = self.parse_var()?;
-                    ast.body.push(Ast::new(Type::ConstVar(var)));
+                    self.parse_var().map(|var| Ast::new(Type::ConstVar(var)))
                 }
                 TokenType::Keyword(KeyWords::Let) => {
-                    let var = self.parse_var()?;
-                    ast.body.push(Ast::new(Type::Variable(var)));
-                }
-                TokenType::Keyword(KeyWords::Fn) => {
-                    ast.body.push(self.parse_fn()?);
-                }
-                TokenType::OpenCurlyBracket => {
-                    ast.body.push(self.parse_block()?);
+                    self.parse_var().map(|var| Ast::new(Type::Variable(var)))
                 }
-                TokenType::Identifier => {
-                    ast.body
-                        .push(Ast::new(Type::FunctionCall(self.parse_fn_call()?)));
+                TokenType::Keyword(KeyWords::Fn) => self.parse_fn(),
+                TokenType::Keyword(KeyWords::Struct) => {
+                    self.parse_struct().map(|struct_| Ast::new(Type::Struct(struct_)))
                 }
+                TokenType::OpenCurlyBracket => self.parse_block(),
+                TokenType::Identifier => self
+                    .parse_fn_call()
+                    .map(|call| Ast::new(Type::FunctionCall(call))),
                 TokenType::Comment => {
                     continue;
                 }
-                token => todo!("Haven't added parsing for these tokens yet {token:#?}"),
+                TokenType::DocComment => {
+                    self.docs.push(token.value.to_string());
+                    continue;
+                }
+                TokenType::Invalid => Err(invalid_token(token)),
+                _ => Err(unexpected_token(token)),
+            };
+            match item {
+                Ok(item) => ast.body.push(item),
+                // The fields are separated by commas, so a struct is skipped as a whole
+                Err(error) if is_struct => {
+                    self.errors.push(error);
+                    self.recover_struct();
+                }
+                Err(error) => {
+                    self.errors.push(error);
+                    self.recover(start);
+                }
             }
+            // Docs only belong to the item right below them
+            self.docs.clear();
         }
-        Ok(ast)
+        (ast, std::mem::take(&mut self.errors))
     }
 }
 
@@ -431,97 +902,112 @@ impl ParseTokens for Parser {
         let prev = self.prev_token.clone().unwrap();
         let mut var = Variable::new();
         // Assigning the line of the variable early so It can be used for errors.
-        var.line(prev.line);
-        // Retrieve all the tokens up untile the semicolon.
+        var.line(prev.line).span(prev.span);
+        // Only constants can be documented
+        if prev.token_type == TokenType::Keyword(KeyWords::Const) {
+            var.docs(std::mem::take(&mut self.docs));
+        }
+        self.skip_comments();
+        let Some(name) = self.next() else {
+            return Err(non_ending_variable(prev.value, prev.span));
+        };
+        if name.token_type != TokenType::Identifier {
+            return Err(invalid_var_syntax_token(name));
+        }
+        var.name(name.value)?;
+
+        self.skip_comments();
+        if self.peak_nth(0).is_some_and(|token| token.token_type == TokenType::Colon) {
+            self.next();
+            var.annotation(self.parse_annotation()?);
+            self.skip_comments();
+        }
+
+        match self.next() {
+            Some(token) if token.token_type == TokenType::Operator(Operators::Eq) => {}
+            // A variable without a value starts with the default value of its type: let some: number;
+            Some(token) if token.token_type == TokenType::SemiColon => {
+                let Some(annotation) = &var.annotation else {
+                    return Err(missing_type_annotation(var.name, prev.span.to(&token.span)));
+                };
+                var.type_(TypeVar::default_of(annotation))?;
+                var.span(prev.span.to(&token.span));
+                return Ok(var);
+            }
+            Some(token) => return Err(invalid_var_syntax_token(token)),
+            None => return Err(non_ending_variable(var.name, prev.span)),
+        }
+
+        let value = self.parse_expr()?;
+        let value_span = value.span;
+        var.type_(TypeVar::from_expr(value))?;
+        // The type of a identifier or call isn't known yet, so it has to be written down
+        if var.annotation.is_none()
+            && matches!(var.type_, TypeVar::Identifier(_) | TypeVar::FunctionCall(_))
+        {
+            return Err(missing_value_annotation(var.name, prev.span.to(&value_span)));
+        }
+        // Only literals have a known type here, other values are checked once they are compiled
+        if let (Some(annotation), Some(found)) = (&var.annotation, var.type_.literal_type()) {
+            if *annotation != found {
+                return Err(mismatched_type_annotation(
+                    var.name,
+                    annotation,
+                    &found,
+                    prev.span.to(&value_span),
+                ));
+            }
+        }
+
         // Considering the end of every variable must be a SemiColon
-        let end_of_var = self.up_until_token(TokenType::SemiColon);
-        match end_of_var {
-            Some(tokens) => {
-                let mut parser = Parser::new(tokens);
-                while let Some(token) = parser.next() {
-                    match token.token_type {
-                        TokenType::Identifier => {
-                            // We assign the name and use the question mark operator which will
-                            // force returning the assign error if there is one.
-                            let assign_ = var.name(token.value.clone());
-                            match assign_ {
-                                Ok(()) => (),
-                                Err(_) => {
-                                    let Some(parse_next) = parser.next() else {
-                                        return Err(invalid_var_syntax_token(token))
-                                    };
-                                    match parse_next.token_type {
-                                        TokenType::SemiColon => {
-                                            let _ = var.type_(TypeVar::Identifier(token.value));
-                                        }
-                                        TokenType::OpenBrace => {
-                                            parser.advance_back(1);
-                                            let _ = var.type_(TypeVar::FunctionCall(
-                                                parser.parse_fn_call()?,
-                                            ));
-                                        }
-                                        _ => return Err(invalid_var_syntax_token(token)),
-                                    }
-                                }
-                            }
-                        }
-                        TokenType::String => {
-                            // We assign the type and use the question mark operator which will
-                            // force returning the assign error if there is one.
-                            var.type_(TypeVar::String(token.value))?;
-                        }
-                        TokenType::Number => {
-                            // We assign the type and use the question mark operator which will
-                            // force returning the assign error if there is one.
-                            var.type_(TypeVar::parse_number(token.value))?;
-                        }
-                        TokenType::OpenBracket => {
-                            var.type_(parser.parse_array()?)?;
-                        }
-                        TokenType::Operator(Operators::Eq) => {}
-                        TokenType::SemiColon => {
-                            return Ok(var);
-                        }
-                        // If we find any token that shouldn't be there we return and error
-                        _ => return Err(invalid_var_syntax_token(token)),
-                    }
-                }
+        self.skip_comments();
+        match self.next() {
+            Some(end) if end.token_type == TokenType::SemiColon => {
+                var.span(prev.span.to(&end.span));
                 Ok(var)
             }
-            None => Err(non_ending_variable(prev.value, prev.line)),
+            _ => Err(non_ending_variable(var.name, prev.span)),
         }
     }
     fn parse_block(&mut self) -> Result<Ast, ErrorBuilder> {
         let mut ast = Ast::new(Type::Block);
-        let mut line = 0;
+        let mut span = Span::default();
         while let Some(token) = self.next() {
-            line += token.line;
-            match token.token_type {
+            span = token.span;
+            let start = self.current_position;
+            let item = match token.token_type {
                 TokenType::Keyword(KeyWords::Use) => {
-                    let use_ = Ast::new(Type::Use(self.parse_use()?));
-                    ast.body.push(use_);
+                    self.parse_use().map(|use_| Ast::new(Type::Use(use_)))
                 }
                 TokenType::Keyword(KeyWords::Let) => {
-                    let ast_var = Ast::new(Type::Variable(self.parse_var()?));
-                    ast.body.push(ast_var);
-                }
-                TokenType::Keyword(KeyWords::Fn) => {
-                    ast.body.push(self.parse_fn()?);
+                    self.parse_var().map(|var| Ast::new(Type::Variable(var)))
                 }
-                TokenType::Identifier => {
-                    ast.body
-                        .push(Ast::new(Type::FunctionCall(self.parse_fn_call()?)));
+                TokenType::Keyword(KeyWords::Fn) => self.parse_fn(),
+                // A identifier followed by a operator, index or field is a assignment, anything
+                // else has to be a call
+                TokenType::Identifier => match self.peak_nth(0).map(|next| next.token_type) {
+                    Some(TokenType::Operator(_) | TokenType::OpenBracket | TokenType::Dot) => self
+                        .parse_assign()
+                        .map(|assign| Ast::new(Type::Assign(assign))),
+                    _ => self
+                        .parse_fn_call()
+                        .map(|call| Ast::new(Type::FunctionCall(call))),
+                },
+                TokenType::OpenCurlyBracket => self.parse_block(),
+                TokenType::Keyword(KeyWords::Return) => self
+                    .parse_return()
+                    .map(|return_| Ast::new(Type::Return(return_))),
+                TokenType::Keyword(KeyWords::If) => {
+                    self.parse_statement().map(|if_| Ast::new(Type::Logic(if_)))
                 }
-                TokenType::OpenCurlyBracket => {
-                    ast.body.push(self.parse_block()?);
+                TokenType::Keyword(KeyWords::While) => {
+                    self.parse_while().map(|while_| Ast::new(Type::While(while_)))
                 }
-                TokenType::Keyword(KeyWords::Return) => {
-                    let return_ = Ast::new(Type::Return(self.parse_return()?));
-                    ast.body.push(return_);
+                TokenType::Keyword(KeyWords::For) => {
+                    self.parse_for().map(|for_| Ast::new(Type::For(for_)))
                 }
-                TokenType::Keyword(KeyWords::If) => {
-                    let if_ = Ast::new(Type::Logic(self.parse_statement()?));
-                    ast.body.push(if_);
+                TokenType::Keyword(KeyWords::Break | KeyWords::Continue) => {
+                    self.parse_loop_control().map(Ast::new)
                 }
                 TokenType::CloseCurlyBracket => {
                     return Ok(ast);
@@ -529,16 +1015,30 @@ impl ParseTokens for Parser {
                 TokenType::Comment => {
                     continue;
                 }
-                token => todo!("Add parsing for these tokens {:#?}", token),
+                TokenType::DocComment => {
+                    self.docs.push(token.value.to_string());
+                    continue;
+                }
+                TokenType::Invalid => Err(invalid_token(token)),
+                _ => Err(unexpected_token(token)),
+            };
+            match item {
+                Ok(item) => ast.body.push(item),
+                Err(error) => {
+                    self.errors.push(error);
+                    self.recover(start);
+                }
             }
+            // Docs only belong to the item right below them
+            self.docs.clear();
         }
-        Err(invalid_function_body_syntax("".to_string(), line))
+        Err(invalid_function_body_syntax(Symbol::EMPTY, span))
     }
     fn parse_args(&mut self) -> Result<Vec<Arg>, ErrorBuilder> {
         let prev = self.prev_token.clone().unwrap();
 
         let Some(tokens_until_close) = self.up_until_token(TokenType::CloseBrace) else {
-            return Err(args_no_close(None, prev.line));
+            return Err(args_no_close(prev.span));
         };
 
         let mut args = Vec::new();
@@ -550,9 +1050,11 @@ impl ParseTokens for Parser {
                     args.push(current_arg.clone());
                     current_arg.clear_type();
                     current_arg.clear_value();
+                    current_arg.clear_span();
                 }
                 TokenType::Keyword(keyword) => match keyword {
                     KeyWords::Number => {
+                        current_arg.extend_span(token.span);
                         let ass_type = current_arg.assign_type(TypesArg::Number);
                         if ass_type.is_err() {
                             return Err(invalid_var_syntax_token(token));
@@ -560,15 +1062,29 @@ impl ParseTokens for Parser {
                     }
 
                     KeyWords::String => {
+                        current_arg.extend_span(token.span);
                         let ass_type = current_arg.assign_type(TypesArg::String);
                         if ass_type.is_err() {
                             return Err(invalid_var_syntax_token(token));
                         }
                     }
-                    _ => todo!("Add a good error message for this case"),
+                    KeyWords::Char => {
+                        current_arg.extend_span(token.span);
+                        let ass_type = current_arg.assign_type(TypesArg::Char);
+                        if ass_type.is_err() {
+                            return Err(invalid_var_syntax_token(token));
+                        }
+                    }
+                    _ => return Err(invalid_argument_type(token)),
                 },
                 TokenType::Identifier => {
-                    let val = current_arg.assign_value(token.value.clone());
+                    current_arg.extend_span(token.span);
+                    // Two identifiers in a row are the struct and the name: Point p
+                    if current_arg.type_ == TypesArg::None && !current_arg.value.is_empty() {
+                        let _ = current_arg.assign_type(TypesArg::Struct(current_arg.value));
+                        current_arg.clear_value();
+                    }
+                    let val = current_arg.assign_value(token.value);
                     if current_arg.type_ == TypesArg::None {
                         let _ = current_arg.assign_type(TypesArg::None);
                     }
@@ -578,7 +1094,8 @@ impl ParseTokens for Parser {
                     }
                 }
                 TokenType::String => {
-                    let val = current_arg.assign_value(token.value.clone());
+                    current_arg.extend_span(token.span);
+                    let val = current_arg.assign_value(token.value);
                     if val.is_err() {
                         return Err(invalid_var_syntax_token(token));
                     }
@@ -588,7 +1105,13 @@ impl ParseTokens for Parser {
                     }
                 }
                 TokenType::Number => {
-                    let val = current_arg.assign_value(token.value.clone());
+                    current_arg.extend_span(token.span);
+                    // The value is stored as a plain decimal number so codegen doesn't have to
+                    // know about the different radixes.
+                    let Some(num) = TypeVar::parse_int(token.value.as_str()) else {
+                        return Err(number_out_of_range(token.value, token.span));
+                    };
+                    let val = current_arg.assign_value(Symbol::intern(&num.to_string()));
                     if val.is_err() {
                         // Todo: Add better error for this case
                         return Err(invalid_var_syntax_token(token));
@@ -598,42 +1121,56 @@ impl ParseTokens for Parser {
                         return Err(invalid_var_syntax_token(token));
                     }
                 }
+                TokenType::Char => {
+                    current_arg.extend_span(token.span);
+                    let val = current_arg.assign_value(token.value);
+                    if val.is_err() {
+                        return Err(invalid_var_syntax_token(token));
+                    }
+                    let ass_type = current_arg.assign_type(TypesArg::Char);
+                    if ass_type.is_err() {
+                        return Err(invalid_var_syntax_token(token));
+                    }
+                }
                 TokenType::CloseBrace => {
                     if !current_arg.value.is_empty() {
                         args.push(current_arg.clone());
                         current_arg.clear_type();
                         current_arg.clear_value();
+                        current_arg.clear_span();
                     }
                     return Ok(args);
                 }
                 TokenType::OpenBrace => {
                     continue;
                 }
-                TokenType::Comment => {
+                TokenType::Comment | TokenType::DocComment => {
                     continue;
                 }
                 // Todo: Invalid argument token error
                 _ => {
-                    return Err(invalid_function_syntax_missing_id(prev.line));
+                    return Err(invalid_function_syntax_missing_id(prev.span));
                 }
             }
         }
         // This would be and error because we never got and closing brace
-        Err(args_no_close(None, prev.line))
+        Err(args_no_close(prev.span))
     }
     fn parse_fn(&mut self) -> Result<Ast, ErrorBuilder> {
         let prev = self.prev_token.clone().unwrap();
+        // Taken before the body is parsed, otherwise the docs would end up on a nested function
+        let docs = std::mem::take(&mut self.docs);
 
         let Some(next) = self.next() else {
-            return Err(invalid_function_syntax_missing_id(prev.line));
+            return Err(invalid_function_syntax_missing_id(prev.span));
         };
         if next.token_type != TokenType::Identifier {
-            return Err(invalid_function_syntax_missing_id(prev.line));
+            return Err(invalid_function_syntax_missing_id(prev.span));
         }
 
         let args = self.parse_args()?;
         let Some(body) = self.next() else {
-            return Err(invalid_function_syntax_missing_id(prev.line));
+            return Err(invalid_function_syntax_missing_id(prev.span));
         };
 
         let mut return_type = ReturnTypes::None;
@@ -643,25 +1180,46 @@ impl ParseTokens for Parser {
             TokenType::Keyword(KeyWords::Number) => {
                 return_type = ReturnTypes::Number;
                 let Some(next) = self.next() else {
-                    return Err(invalid_function_body_syntax(next.value, prev.line));
+                    return Err(invalid_function_body_syntax(next.value, prev.span));
                 };
                 if next.token_type != TokenType::OpenCurlyBracket {
-                    return Err(invalid_function_body_syntax(next.value, prev.line));
+                    return Err(invalid_function_body_syntax(next.value, prev.span));
                 }
             }
             TokenType::Keyword(KeyWords::String) => {
                 return_type = ReturnTypes::String;
                 let Some(next) = self.next() else {
-                    return Err(invalid_function_body_syntax(next.value, prev.line));
+                    return Err(invalid_function_body_syntax(next.value, prev.span));
                 };
                 if next.token_type != TokenType::OpenCurlyBracket {
-                    return Err(invalid_function_body_syntax(next.value, prev.line));
+                    return Err(invalid_function_body_syntax(next.value, prev.span));
                 }
             }
-            _ => return Err(invalid_function_body_syntax(next.value, prev.line)),
+            TokenType::Keyword(KeyWords::Char) => {
+                return_type = ReturnTypes::Char;
+                let Some(next) = self.next() else {
+                    return Err(invalid_function_body_syntax(next.value, prev.span));
+                };
+                if next.token_type != TokenType::OpenCurlyBracket {
+                    return Err(invalid_function_body_syntax(next.value, prev.span));
+                }
+            }
+            // Any other type is the name of a struct
+            TokenType::Identifier => {
+                return_type = ReturnTypes::Struct(body.value);
+                let Some(next) = self.next() else {
+                    return Err(invalid_function_body_syntax(next.value, prev.span));
+                };
+                if next.token_type != TokenType::OpenCurlyBracket {
+                    return Err(invalid_function_body_syntax(next.value, prev.span));
+                }
+            }
+            _ => return Err(invalid_function_body_syntax(next.value, prev.span)),
         }
 
         let body = Some(Box::from(self.parse_block()?));
+        // The block ends on the closing curly bracket, which is the last token we consumed
+        let end = self.prev_token.as_ref().map_or(prev.span, |token| token.span);
 
         let ast = Ast::new(Type::Function(Func {
             name: next.value,
@@ -669,52 +1227,91 @@ impl ParseTokens for Parser {
             body,
             return_type,
             line: prev.line,
+            span: prev.span.to(&end),
+            docs,
         }));
         Ok(ast)
     }
-    fn parse_array(&mut self) -> Result<TypeVar, ErrorBuilder> {
-        let mut current_var = String::new();
-        let mut values = Vec::new();
+    fn parse_struct(&mut self) -> Result<Struct, ErrorBuilder> {
+        let prev = self.prev_token.clone().unwrap();
+        let docs = std::mem::take(&mut self.docs);
 
-        let mut line = 0;
-        for token in self.by_ref() {
-            line = token.line;
-            match token.token_type.clone() {
-                TokenType::Comma => {
-                    let num: Result<i32, _> = current_var.parse();
-                    if num.is_ok() {
-                        values.push(TypeVar::Number(num.unwrap()));
-                        current_var = "".into();
-                        continue;
-                    }
-                    values.push(TypeVar::Number(num.unwrap()));
-                    current_var = "".into();
-                }
-                TokenType::Identifier => {
-                    current_var = token.value;
-                }
-                TokenType::CloseBracket => {
-                    return Ok(TypeVar::Arr { values });
-                }
-                TokenType::Number => {
-                    if !current_var.is_empty() {
-                        return Err(invalid_var_syntax_token(token));
-                    }
-                    current_var = token.value;
+        self.skip_comments();
+        let Some(name) = self.next().filter(|name| name.token_type == TokenType::Identifier) else {
+            return Err(invalid_struct(prev.span));
+        };
+        self.skip_comments();
+        match self.next() {
+            Some(open) if open.token_type == TokenType::OpenCurlyBracket => {}
+            _ => return Err(invalid_struct(prev.span.to(&name.span))),
+        }
+
+        let mut fields: Vec<Field> = Vec::new();
+        loop {
+            self.skip_comments();
+            // Also allows a trailing comma: struct Point { x: number, y: number, }
+            let field = match self.next() {
+                Some(close)
+                    if close.token_type == TokenType::CloseCurlyBracket && !fields.is_empty() =>
+                {
+                    return Ok(Struct::new(name.value, fields, prev.span.to(&close.span), docs));
                 }
-                TokenType::Comment => {
-                    continue;
+                Some(field) if field.token_type == TokenType::Identifier => field,
+                Some(token) => return Err(invalid_struct(token.span)),
+                None => return Err(invalid_struct(prev.span.to(&name.span))),
+            };
+            if fields.iter().any(|written| written.name == field.value) {
+                return Err(duplicate_field(name.value, field.value, field.span));
+            }
+            self.skip_comments();
+            match self.next() {
+                Some(colon) if colon.token_type == TokenType::Colon => {}
+                _ => return Err(invalid_struct(field.span)),
+            }
+            let type_ = self.parse_annotation()?;
+            let end = self.prev_token.as_ref().map_or(field.span, |token| token.span);
+            fields.push(Field {
+                name: field.value,
+                type_,
+                span: field.span.to(&end),
+            });
+
+            self.skip_comments();
+            match self.peak_nth(0) {
+                Some(comma) if comma.token_type == TokenType::Comma => {
+                    self.next();
                 }
-                TokenType::String => {
-                    if !current_var.is_empty() {
-                        return Err(invalid_var_syntax_token(token));
-                    }
-                    current_var = token.value;
+                Some(close) if close.token_type == TokenType::CloseCurlyBracket => {}
+                Some(token) => return Err(invalid_struct(token.span)),
+                None => return Err(invalid_struct(prev.span.to(&name.span))),
+            }
+        }
+    }
+    fn parse_array(&mut self) -> Result<Vec<Expr>, ErrorBuilder> {
+        let open = self.prev_token.clone().unwrap();
+        let mut values = Vec::new();
+
+        loop {
+            self.skip_comments();
+            // Also allows a trailing comma: [1, 2, 3,]
+            match self.peak_nth(0) {
+                Some(token) if token.token_type == TokenType::CloseBracket => {
+                    self.next();
+                    return Ok(values);
                 }
-                _ => return Err(invalid_var_syntax_token(token)),
+                Some(_) => {}
+                None => return Err(invalid_arr_no_end(open.span)),
+            }
+
+            values.push(self.parse_expr()?);
+            self.skip_comments();
+            match self.next() {
+                Some(token) if token.token_type == TokenType::Comma => continue,
+                Some(token) if token.token_type == TokenType::CloseBracket => return Ok(values),
+                Some(token) => return Err(invalid_var_syntax_token(token)),
+                None => return Err(invalid_arr_no_end(open.span)),
             }
         }
-        Err(invalid_arr_no_end(line))
     }
     fn parse_fn_call(&mut self) -> Result<Func, ErrorBuilder> {
         let prev = self.prev_token.clone().unwrap();
@@ -723,119 +1320,199 @@ impl ParseTokens for Parser {
         match next {
             Some(token) => {
                 if token.token_type != TokenType::OpenBrace {
-                    return Err(invalid_function_call(prev.value, prev.line));
+                    return Err(invalid_function_call(prev.value, prev.span));
                 }
             }
-            None => return Err(invalid_function_call(prev.value, prev.line)),
+            None => return Err(invalid_function_call(prev.value, prev.span)),
         }
 
-        let func = Func {
-            name: prev.value.clone(),
-            args: self.parse_args()?,
-            body: None,
-            return_type: ReturnTypes::None,
-            line: prev.line,
-        };
+        let mut func = self.parse_call()?;
 
         let Some(close) = self.next() else {
-            return Err(non_ending_variable(prev.value, prev.line));
+            return Err(non_ending_variable(prev.value, prev.span));
         };
 
         if close.token_type != TokenType::SemiColon {
-            return Err(non_ending_variable(prev.value, prev.line));
+            return Err(non_ending_variable(prev.value, prev.span));
         }
 
+        func.span = prev.span.to(&close.span);
         Ok(func)
     }
-    fn parse_return(&mut self) -> Result<Return, ErrorBuilder> {
-        let prev = &self.prev_token.clone().unwrap();
-        let Some(up_until) = &self.up_until_token(TokenType::SemiColon) else {
-            return Err(invalid_return_no_end(prev.line));
+    fn parse_assign(&mut self) -> Result<Assign, ErrorBuilder> {
+        let prev = self.prev_token.clone().unwrap();
+        let place = Expr::new(ExprKind::Identifier(prev.value), prev.span);
+        let target = match self.parse_postfix(place)?.kind {
+            ExprKind::Field { value, field } => AssignTarget::Field {
+                value: *value,
+                field,
+            },
+            ExprKind::Index { array, index } => AssignTarget::Index {
+                array: *array,
+                index: *index,
+            },
+            _ => AssignTarget::Variable(prev.value),
         };
-        for token in up_until {
-            match token.token_type {
-                TokenType::Number => {
-                    return Ok(Return {
-                        value: token.value.clone(),
-                        type_: ReturnTypes::Number,
-                        line: token.line,
-                    });
-                }
-                TokenType::String => {
-                    return Ok(Return {
-                        value: token.value.clone(),
-                        type_: ReturnTypes::String,
-                        line: token.line,
-                    });
-                }
-                TokenType::Identifier => {
-                    return Ok(Return {
-                        value: token.value.clone(),
-                        type_: ReturnTypes::Identifier,
-                        line: token.line,
-                    });
-                }
-                TokenType::SemiColon => {
-                    return Ok(Return {
-                        value: "void".to_string(),
-                        type_: ReturnTypes::None,
-                        line: token.line,
-                    });
-                }
-                _ => todo!("This is currently not supported yet, might get added in the future..."),
+
+        let Some(TokenType::Operator(op)) = self.next().map(|token| token.token_type) else {
+            return Err(invalid_assignment(prev.value, prev.span));
+        };
+        let op = match op {
+            Operators::Eq => None,
+            op => match BinOp::from_compound(&op) {
+                Some(op) => Some(op),
+                None => return Err(invalid_assignment(prev.value, prev.span)),
+            },
+        };
+        let value = self.parse_expr()?;
+
+        self.skip_comments();
+        match self.next() {
+            Some(end) if end.token_type == TokenType::SemiColon => {
+                Ok(Assign::new(target, op, value, prev.span.to(&end.span)))
             }
+            _ => Err(non_ending_statement(prev.value, prev.span)),
+        }
+    }
+    fn parse_return(&mut self) -> Result<Return, ErrorBuilder> {
+        let prev = self.prev_token.clone().unwrap();
+        self.skip_comments();
+        // Returning nothing: return;
+        if let Some(end) = self
+            .peak_nth(0)
+            .filter(|token| token.token_type == TokenType::SemiColon)
+        {
+            self.next();
+            return Ok(Return::new(None, end.line, prev.span.to(&end.span)));
         }
 
-        Err(invalid_return_no_end(prev.line))
+        let expr = self.parse_expr()?;
+        let line = expr.span.line;
+        self.skip_comments();
+        match self.next() {
+            // The return reaches from the return keyword up until the semicolon
+            Some(end) if end.token_type == TokenType::SemiColon => {
+                Ok(Return::new(Some(expr), line, prev.span.to(&end.span)))
+            }
+            _ => Err(invalid_return_no_end(prev.span)),
+        }
     }
     fn parse_statement(&mut self) -> Result<Logic, ErrorBuilder> {
         let prev = self.prev_token.clone().unwrap();
         let case = self.get_case()?;
-        let block = self.next().unwrap();
+        let Some(block) = self.next() else {
+            return Err(invalid_if_statement_body(prev.span));
+        };
 
         match block.token_type {
             TokenType::OpenCurlyBracket => {
                 let do_ = &self.parse_block()?;
+                let do_end = self.prev_token.as_ref().map_or(prev.span, |token| token.span);
                 let Some(check_else) = self.next() else {
-                    return Ok(Logic::new(case,None, do_.clone()));
+                    return Ok(Logic::new(case,None, do_.clone(), prev.span.to(&do_end)));
                 };
                 match check_else.token_type {
                     TokenType::Keyword(KeyWords::Else) => match self.next() {
                         Some(val) => match val.token_type {
                             TokenType::OpenCurlyBracket => {
                                 let body = &self.parse_block()?;
-                                Ok(Logic::new(case, Some(Box::from(body.clone())), do_.clone()))
+                                let else_end = self.prev_token.as_ref().map_or(do_end, |token| token.span);
+                                Ok(Logic::new(case, Some(Box::from(body.clone())), do_.clone(), prev.span.to(&else_end)))
                             }
-                            _ => Err(invalid_if_statement_body(prev.line)),
+                            // else if, the rest of the chain becomes the else of this statement
+                            TokenType::Keyword(KeyWords::If) => {
+                                let else_if = self.parse_statement()?;
+                                let span = prev.span.to(&else_if.span);
+                                let else_ = Ast::new(Type::Logic(else_if));
+                                Ok(Logic::new(case, Some(Box::from(else_)), do_.clone(), span))
+                            }
+                            _ => Err(invalid_if_statement_body(prev.span)),
                         },
-                        _ => Err(invalid_if_statement_body(prev.line)),
+                        _ => Err(invalid_if_statement_body(prev.span)),
                     },
                     _ => {
                         self.advance_back(1);
-                        Ok(Logic::new(case, None, do_.clone()))
+                        Ok(Logic::new(case, None, do_.clone(), prev.span.to(&do_end)))
                     }
                 }
             }
-            _ => Err(invalid_if_statement_body(prev.line)),
+            _ => Err(invalid_if_statement_body(prev.span)),
+        }
+    }
+    fn parse_while(&mut self) -> Result<While, ErrorBuilder> {
+        let prev = self.prev_token.clone().unwrap();
+        let condition = self.get_case()?;
+
+        match self.next() {
+            Some(token) if token.token_type == TokenType::OpenCurlyBracket => {}
+            _ => return Err(invalid_while_body(prev.span)),
+        }
+        let body = self.parse_block()?;
+        let end = self.prev_token.as_ref().map_or(prev.span, |token| token.span);
+
+        Ok(While::new(condition, body, prev.span.to(&end)))
+    }
+    fn parse_for(&mut self) -> Result<For, ErrorBuilder> {
+        let prev = self.prev_token.clone().unwrap();
+        let Some(var) = self.next().filter(|token| token.token_type == TokenType::Identifier) else {
+            return Err(invalid_for_loop(prev.span));
+        };
+        match self.next() {
+            Some(token) if token.token_type == TokenType::Keyword(KeyWords::In) => {}
+            _ => return Err(invalid_for_loop(prev.span)),
+        }
+
+        let start = self.parse_expr()?;
+        let iter = match self.peak_nth(0) {
+            Some(token) if token.token_type == TokenType::DotDot => {
+                self.next();
+                ForIter::Range {
+                    start,
+                    end: self.parse_expr()?,
+                }
+            }
+            _ => ForIter::Array(start),
+        };
+
+        match self.next() {
+            Some(token) if token.token_type == TokenType::OpenCurlyBracket => {}
+            _ => return Err(invalid_for_loop(prev.span)),
+        }
+        let body = self.parse_block()?;
+        let end = self.prev_token.as_ref().map_or(prev.span, |token| token.span);
+
+        Ok(For::new(var.value, iter, body, prev.span.to(&end)))
+    }
+    fn parse_loop_control(&mut self) -> Result<Type, ErrorBuilder> {
+        let prev = self.prev_token.clone().unwrap();
+        let Some(end) = self.next().filter(|token| token.token_type == TokenType::SemiColon) else {
+            return Err(non_ending_statement(prev.value, prev.span));
+        };
+
+        let span = prev.span.to(&end.span);
+        match prev.token_type {
+            TokenType::Keyword(KeyWords::Continue) => Ok(Type::Continue(span)),
+            _ => Ok(Type::Break(span)),
         }
     }
     fn parse_use(&mut self) -> Result<Use, ErrorBuilder> {
+        let use_token = self.prev_token.clone().unwrap();
         let tokens = &self.next();
         let prev = &self.prev_token.clone().unwrap();
         match tokens {
             Some(file) => match file.token_type {
                 TokenType::String => {
                     let Some(end_use) = self.next() else {
-                                return Err(invalid_use(Some(file.value.clone()), prev.line))
+                                return Err(invalid_use(Some(file.value), prev.span))
                     };
                     if end_use.token_type != TokenType::SemiColon {
-                        return Err(invalid_use(Some(file.value.clone()), prev.line));
+                        return Err(invalid_use(Some(file.value), prev.span));
                     }
-                    Ok(Use::new(file.value.clone()))
+                    Ok(Use::new(file.value.to_string(), use_token.span.to(&end_use.span)))
                 }
-                _ => Err(invalid_use(None, prev.line)),
+                _ => Err(invalid_use(None, prev.span)),
             },
-            None => Err(invalid_use(None, prev.line)),
+            None => Err(invalid_use(None, prev.span)),
         }
     }
 }
@@ -0,0 +1,62 @@
+use std::sync::RwLock;
+
+/// The id of a file loaded into the [`SourceMap`], every token and span keeps the id of the file
+/// it was lexed from.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct FileId(usize);
+
+/// A file loaded by the compiler
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    pub name: String,
+    pub source: String,
+}
+
+/// Keeps track of every file that was loaded, so a span can be traced back to its file.
+#[derive(Debug, Default)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+}
+
+impl SourceMap {
+    pub const fn new() -> SourceMap {
+        SourceMap { files: Vec::new() }
+    }
+
+    /// Adds a file to the source map and returns the id it can be found with
+    pub fn add(&mut self, name: impl AsRef<str>, source: impl AsRef<str>) -> FileId {
+        self.files.push(SourceFile {
+            name: name.as_ref().to_string(),
+            source: source.as_ref().to_string(),
+        });
+        FileId(self.files.len() - 1)
+    }
+
+    pub fn get(&self, file: FileId) -> Option<&SourceFile> {
+        self.files.get(file.0)
+    }
+
+    pub fn name(&self, file: FileId) -> Option<&str> {
+        self.get(file).map(|file| file.name.as_str())
+    }
+}
+
+/// The files the compiler loaded, errors use this to find the name of the file they happened in.
+pub static SOURCE_MAP: RwLock<SourceMap> = RwLock::new(SourceMap::new());
+
+/// Loads a file into the [`SOURCE_MAP`]
+pub fn load_file(name: impl AsRef<str>, source: impl AsRef<str>) -> FileId {
+    SOURCE_MAP
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .add(name, source)
+}
+
+/// The name of a file in the [`SOURCE_MAP`]
+pub fn file_name(file: FileId) -> Option<String> {
+    SOURCE_MAP
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .name(file)
+        .map(str::to_string)
+}
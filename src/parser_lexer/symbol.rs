@@ -0,0 +1,149 @@
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display},
+    sync::{LazyLock, RwLock},
+};
+
+/// A interned string.
+///
+/// Every identifier and token value is stored once in the [`INTERNER`], a symbol is just the
+/// index of that string. This makes symbols cheap to copy and comparing two symbols only compares
+/// two numbers instead of two strings.
+///
+/// # Example
+/// ```
+/// use lepa::parser_lexer::symbol::Symbol;
+///
+/// let a = Symbol::intern("main");
+/// let b = Symbol::intern("main");
+/// assert_eq!(a, b);
+/// assert_eq!(a, "main");
+/// assert_eq!(a.as_str(), "main");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Symbol(u32);
+
+/// Keeps every interned string, the strings are leaked so they can be handed out for the rest of
+/// the compilation.
+pub struct Interner {
+    names: HashMap<&'static str, Symbol>,
+    strings: Vec<&'static str>,
+}
+
+impl Interner {
+    pub fn new() -> Interner {
+        let mut interner = Interner {
+            names: HashMap::new(),
+            strings: Vec::new(),
+        };
+        // The empty string is always the first symbol so it can be used as the default
+        interner.intern("");
+        interner
+    }
+
+    /// Returns the symbol of the string, the string is only stored if it wasn't interned before
+    pub fn intern(&mut self, string: &str) -> Symbol {
+        if let Some(symbol) = self.names.get(string) {
+            return *symbol;
+        }
+        let string: &'static str = Box::leak(string.to_owned().into_boxed_str());
+        let symbol = Symbol(self.strings.len() as u32);
+        self.strings.push(string);
+        self.names.insert(string, symbol);
+        symbol
+    }
+
+    pub fn get(&self, symbol: Symbol) -> &'static str {
+        self.strings[symbol.0 as usize]
+    }
+}
+
+impl Default for Interner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// All the strings interned during this compilation
+pub static INTERNER: LazyLock<RwLock<Interner>> = LazyLock::new(|| RwLock::new(Interner::new()));
+
+impl Symbol {
+    /// The symbol of the empty string
+    pub const EMPTY: Symbol = Symbol(0);
+
+    pub fn intern(string: &str) -> Symbol {
+        // Most strings are already interned, so first try finding it without taking the write lock
+        if let Some(symbol) = INTERNER
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .names
+            .get(string)
+        {
+            return *symbol;
+        }
+        INTERNER
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .intern(string)
+    }
+
+    /// The string the symbol was interned from
+    pub fn as_str(self) -> &'static str {
+        INTERNER
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(self)
+    }
+
+    pub fn is_empty(self) -> bool {
+        self == Symbol::EMPTY
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(value: &str) -> Self {
+        Symbol::intern(value)
+    }
+}
+
+impl From<String> for Symbol {
+    fn from(value: String) -> Self {
+        Symbol::intern(&value)
+    }
+}
+
+impl AsRef<str> for Symbol {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for Symbol {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl Display for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Debug for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
@@ -36,11 +36,56 @@ pub mod test_lexer {
             // Verify that the second token is an MoreEq
             assert_eq!(lex[1].token_type, TokenType::Operator(Operators::MoreEq));
         }
+
+        #[test]
+        fn test_comparisons_without_spaces() {
+            let lex = Token::lex("a<b".to_string());
+            assert_eq!(lex[0].value, "a");
+            assert_eq!(lex[1].token_type, TokenType::Operator(Operators::Less));
+            assert_eq!(lex[2].value, "b");
+
+            let lex = Token::lex("a>=b".to_string());
+            assert_eq!(lex[1].token_type, TokenType::Operator(Operators::MoreEq));
+            assert_eq!(lex[1].value, ">=");
+
+            let lex = Token::lex("1!=2".to_string());
+            assert_eq!(lex[1].token_type, TokenType::Operator(Operators::NotEq));
+
+            let lex = Token::lex("(1<2)".to_string());
+            assert_eq!(lex[2].token_type, TokenType::Operator(Operators::Less));
+        }
+
+        #[test]
+        fn test_arithmetic() {
+            let lex = Token::lex("a*b%c/d!e".to_string());
+            assert_eq!(lex[1].token_type, TokenType::Star);
+            assert_eq!(lex[3].token_type, TokenType::Percent);
+            assert_eq!(lex[5].token_type, TokenType::Slash);
+            assert_eq!(lex[7].token_type, TokenType::Not);
+            assert_eq!(lex.len(), 9);
+        }
+
+        #[test]
+        fn test_compound_assignment() {
+            let lex = Token::lex("+= -= *= /= %=".to_string());
+            let expected = [
+                Operators::PlusEq,
+                Operators::MinEq,
+                Operators::StarEq,
+                Operators::SlashEq,
+                Operators::PercentEq,
+            ];
+            for (token, op) in lex.iter().zip(expected) {
+                assert_eq!(token.token_type, TokenType::Operator(op));
+            }
+            assert_eq!(lex.len(), 5);
+        }
     }
 
     #[cfg(test)]
     pub mod test_strings {
-        use crate::parser_lexer::lexer::{Lexer, Token, TokenType};
+        use crate::errors::error_messages::lex_error;
+        use crate::parser_lexer::lexer::{LexErrorKind, Lexer, Span, Token, TokenType};
 
         #[test]
         fn test_string() {
@@ -52,6 +97,52 @@ pub mod test_lexer {
             // Verify that the value of the string is correct
             assert_eq!(lex[0].value, "Hello World")
         }
+
+        #[test]
+        fn test_string_escapes() {
+            let input = r#""a \"quoted\" word\n\t\\\0\x41\u{1F600}""#.to_string();
+            let lex = Token::lex(input);
+
+            assert_eq!(lex[0].token_type, TokenType::String);
+            assert_eq!(lex[0].value, "a \"quoted\" word\n\t\\\0A\u{1F600}");
+        }
+
+        #[test]
+        fn test_invalid_escape() {
+            let input = "let\n  \"ab\\q\" ;".to_string();
+            let (lex, errors) = Token::lex_with_errors(input);
+
+            // The string is invalid but the lexer continues after the closing quote
+            assert_eq!(lex[1].token_type, TokenType::Invalid);
+            assert_eq!(lex[1].value, "\"ab\\q\"");
+            assert_eq!(lex[2].token_type, TokenType::SemiColon);
+            assert_eq!(errors.len(), 1);
+            assert_eq!(lex[1].span, Span::new(6, 12, 2, 3));
+            assert_eq!(errors[0].kind, LexErrorKind::InvalidEscape("q".to_string()));
+            // The error points at the escape instead of the opening quote
+            assert_eq!(errors[0].span, Span::new(9, 11, 2, 6));
+            assert!(errors[0].suggestion.is_some());
+            let error = lex_error(&errors[0]);
+            assert!(error.to_string().contains("at column 6"));
+
+            let lex = Token::lex("\"\\x80\" \"\\u{110000}\"".to_string());
+            assert_eq!(lex[0].token_type, TokenType::Invalid);
+            assert_eq!(lex[1].token_type, TokenType::Invalid);
+        }
+
+        #[test]
+        fn test_raw_string() {
+            let input = r###"r"\d+\n" r#"a "quoted" word"#"###.to_string();
+            let lex = Token::lex(input);
+
+            assert_eq!(lex[0].token_type, TokenType::String);
+            assert_eq!(lex[0].value, "\\d+\\n");
+            assert_eq!(lex[1].token_type, TokenType::String);
+            assert_eq!(lex[1].value, "a \"quoted\" word");
+
+            let lex = Token::lex("r#\"no end\"".to_string());
+            assert_eq!(lex[0].token_type, TokenType::Invalid);
+        }
     }
 
     #[cfg(test)]
@@ -66,7 +157,36 @@ pub mod test_lexer {
             // Verify that the first token is a number
             assert_eq!(lex[0].token_type, TokenType::Number);
             // Verify that the value of the number is correct
-            assert_eq!(lex[0].value.parse::<f64>().unwrap(), 10.);
+            assert_eq!(lex[0].value.as_str().parse::<f64>().unwrap(), 10.);
+        }
+
+        #[test]
+        fn test_number_prefixes() {
+            let input = " 0xFF 0b1010 0o17 1_000_000 7".to_string();
+            let lex = Token::lex(input);
+
+            assert!(lex.iter().all(|token| token.token_type == TokenType::Number));
+            assert_eq!(lex[0].value, "0xFF");
+            assert_eq!(lex[1].value, "0b1010");
+            assert_eq!(lex[2].value, "0o17");
+            // Underscores are only there for readability
+            assert_eq!(lex[3].value, "1000000");
+            // A number at the end of the file is still a number
+            assert_eq!(lex[4].value, "7");
+        }
+
+        #[test]
+        fn test_invalid_numbers() {
+            let lex = Token::lex(" 0b102; ".to_string());
+            assert_eq!(lex[0].token_type, TokenType::Invalid);
+            assert_eq!(lex[1].token_type, TokenType::SemiColon);
+
+            let lex = Token::lex(" 0x; ".to_string());
+            assert_eq!(lex[0].token_type, TokenType::Invalid);
+
+            let lex = Token::lex(" 12ab ".to_string());
+            assert_eq!(lex[0].token_type, TokenType::Invalid);
+            assert_eq!(lex.len(), 1);
         }
     }
 
@@ -89,6 +209,27 @@ pub mod test_lexer {
             // Verify that the value of the identifier is correct
             assert_eq!(lex[1].value, "world");
         }
+
+        #[test]
+        fn test_unicode_identifier() {
+            let input = "größe _private δx9 @add".to_string();
+            let lex = Token::lex(input);
+
+            let values: Vec<&str> = lex.iter().map(|token| token.value.as_str()).collect();
+            assert_eq!(values, vec!["größe", "_private", "δx9", "@add"]);
+            assert!(lex.iter().all(|token| token.token_type == TokenType::Identifier));
+        }
+
+        #[test]
+        fn test_identifier_ends_at_operator() {
+            let input = "a:b\"c\"".to_string();
+            let lex = Token::lex(input);
+
+            assert_eq!(lex[0].value, "a");
+            assert_eq!(lex[1].token_type, TokenType::Colon);
+            assert_eq!(lex[2].value, "b");
+            assert_eq!(lex[3].token_type, TokenType::String);
+        }
     }
 
     #[cfg(test)]
@@ -109,5 +250,352 @@ pub mod test_lexer {
             let lex = Token::lex(input.into());
             assert_eq!(lex[0].token_type, TokenType::Keyword(KeyWords::Let));
         }
+
+        #[test]
+        fn test_loops() {
+            let input = "while break continue breaks";
+            let lex = Token::lex(input.into());
+            assert_eq!(lex[0].token_type, TokenType::Keyword(KeyWords::While));
+            assert_eq!(lex[1].token_type, TokenType::Keyword(KeyWords::Break));
+            assert_eq!(lex[2].token_type, TokenType::Keyword(KeyWords::Continue));
+            assert_eq!(lex[3].token_type, TokenType::Identifier);
+        }
+
+        #[test]
+        fn test_for_range() {
+            let input = "for i in 0..10 inner a.b";
+            let lex = Token::lex(input.into());
+            assert_eq!(lex[0].token_type, TokenType::Keyword(KeyWords::For));
+            assert_eq!(lex[2].token_type, TokenType::Keyword(KeyWords::In));
+            // The number stops at the dots instead of taking them as a decimal point
+            assert_eq!(lex[3].token_type, TokenType::Number);
+            assert_eq!(lex[3].value, "0");
+            assert_eq!(lex[4].token_type, TokenType::DotDot);
+            assert_eq!(lex[5].value, "10");
+            assert_eq!(lex[6].token_type, TokenType::Identifier);
+            assert_eq!(lex[8].token_type, TokenType::Dot);
+        }
+    }
+
+    #[cfg(test)]
+    pub mod test_spans {
+        use crate::parser_lexer::lexer::{Lexer, Span, Token};
+
+        #[test]
+        fn test_token_spans() {
+            let input = "let a = 1;\n  a;".to_string();
+            let lex = Token::lex(input);
+
+            assert_eq!(lex[0].span, Span::new(0, 3, 1, 1));
+            assert_eq!(lex[1].span, Span::new(4, 5, 1, 5));
+            assert_eq!(lex[3].span, Span::new(8, 9, 1, 9));
+            // The column starts over after a newline
+            assert_eq!(lex[5].span, Span::new(13, 14, 2, 3));
+            assert_eq!(lex[6].span, Span::new(14, 15, 2, 4));
+        }
+
+        #[test]
+        fn test_span_byte_offsets() {
+            let input = "\"é\" x".to_string();
+            let lex = Token::lex(input);
+
+            // The é takes up two bytes but only one column
+            assert_eq!(lex[0].span, Span::new(0, 4, 1, 1));
+            assert_eq!(lex[1].span, Span::new(5, 6, 1, 5));
+        }
+    }
+
+    #[cfg(test)]
+    pub mod test_lex_errors {
+        use crate::parser_lexer::lexer::{LexErrorKind, Lexer, Span, Token, TokenType};
+
+        #[test]
+        fn test_errors_are_collected() {
+            let input = "let a = $;\nlet b = 0x;\nlet c = 'ab';".to_string();
+            let (lex, errors) = Token::lex_with_errors(input);
+
+            let kinds: Vec<LexErrorKind> = errors.iter().map(|error| error.kind.clone()).collect();
+            assert_eq!(
+                kinds,
+                vec![
+                    LexErrorKind::UnknownChar('$'),
+                    LexErrorKind::MissingDigits("0x".to_string()),
+                    LexErrorKind::MultipleChars,
+                ]
+            );
+            assert_eq!(errors[0].span, Span::new(8, 9, 1, 9));
+            assert_eq!(errors[1].span.line, 2);
+            // The invalid tokens hold the source they were lexed from
+            assert_eq!(lex[3].token_type, TokenType::Invalid);
+            assert_eq!(lex[3].value, "$");
+        }
+
+        #[test]
+        fn test_no_errors() {
+            let input = include_str!("../../sample_code/testing/var.lp").to_string();
+            let (_, errors) = Token::lex_with_errors(input);
+            assert!(errors.is_empty());
+        }
+
+        #[test]
+        fn test_unterminated_string() {
+            let (_, errors) = Token::lex_with_errors("let a = \"never closed;".to_string());
+            assert_eq!(errors[0].kind, LexErrorKind::UnterminatedString);
+            assert_eq!(errors[0].span, Span::new(8, 22, 1, 9));
+        }
+    }
+
+    #[cfg(test)]
+    pub mod test_stream {
+        use crate::parser_lexer::lexer::{Lexer, Token, TokenType};
+
+        #[test]
+        fn test_stream_is_lazy() {
+            // The invalid token at the end is never reached when only the first tokens are taken
+            let input = "let a = 1; $";
+            let tokens: Vec<Token> = Token::stream(input).take(2).collect();
+
+            assert_eq!(tokens.len(), 2);
+            assert_eq!(tokens[1].token_type, TokenType::Identifier);
+            assert_eq!(tokens[1].value, "a");
+        }
+
+        #[test]
+        fn test_stream_matches_lex() {
+            let input = include_str!("../../sample_code/testing/var.lp");
+            let streamed: Vec<Token> = Token::stream(input).collect();
+
+            assert_eq!(streamed, Token::lex(input.to_string()));
+        }
+    }
+
+    #[cfg(test)]
+    pub mod test_lossless {
+        use crate::parser_lexer::lexer::{Lexer, Token, TokenType, TriviaKind};
+
+        fn rebuild(input: &str) -> String {
+            let mut lossless = Token::lossless(input);
+            let mut source: String = lossless.by_ref().map(|token| token.to_string()).collect();
+            for trivia in lossless.trailing_trivia() {
+                source.push_str(trivia.text);
+            }
+            source
+        }
+
+        #[test]
+        fn test_rebuild_source() {
+            let inputs = [
+                include_str!("../../sample_code/main.lp"),
+                include_str!("../../sample_code/testing/var.lp"),
+                include_str!("../../sample_code/testing/comments.lp"),
+                include_str!("../../sample_code/testing/chars.lp"),
+                include_str!("../../sample_code/testing/docs.lp"),
+                "let a =\t\"a\\n\"; \r\n  $ 'ab' /* never closed",
+            ];
+            for input in inputs {
+                assert_eq!(rebuild(input), input);
+            }
+        }
+
+        #[test]
+        fn test_trivia() {
+            let input = "let  a // the a\n/* b */ = 1;  ";
+            let tokens: Vec<_> = Token::lossless(input).collect();
+
+            assert!(tokens[0].leading_trivia.is_empty());
+            assert_eq!(tokens[1].leading_trivia[0].kind, TriviaKind::Whitespace);
+            assert_eq!(tokens[1].leading_trivia[0].text, "  ");
+
+            // The comments are trivia instead of tokens
+            let kinds: Vec<_> = tokens[2].leading_trivia.iter().map(|t| t.kind).collect();
+            assert_eq!(
+                kinds,
+                vec![
+                    TriviaKind::Whitespace,
+                    TriviaKind::Comment,
+                    TriviaKind::Newline,
+                    TriviaKind::Comment,
+                    TriviaKind::Whitespace,
+                ]
+            );
+            assert_eq!(tokens[2].text, "=");
+            assert!(tokens.iter().all(|t| t.token.token_type != TokenType::Comment));
+        }
+
+        #[test]
+        fn test_text_is_source() {
+            let tokens: Vec<_> = Token::lossless("\"a\\tb\" 0x1_F").collect();
+
+            // The value is decoded, the text is what was written
+            assert_eq!(tokens[0].token.value, "a\tb");
+            assert_eq!(tokens[0].text, "\"a\\tb\"");
+            assert_eq!(tokens[1].token.value, "0x1F");
+            assert_eq!(tokens[1].text, "0x1_F");
+        }
+    }
+
+    #[cfg(test)]
+    pub mod test_comments {
+        use crate::parser_lexer::lexer::{KeyWords, LexErrorKind, Lexer, Token, TokenType};
+
+        #[test]
+        fn test_line_comment() {
+            let input = "// hello\nlet ".to_string();
+            let lex = Token::lex(input);
+
+            assert_eq!(lex[0].token_type, TokenType::Comment);
+            assert_eq!(lex[0].value, " hello");
+            // The newline at the end of the comment still counts
+            assert_eq!(lex[1].token_type, TokenType::Keyword(KeyWords::Let));
+            assert_eq!(lex[1].line, 2);
+        }
+
+        #[test]
+        fn test_doc_comment() {
+            let input = "/// docs\n//// not docs\n// also not".to_string();
+            let lex = Token::lex(input);
+
+            assert_eq!(lex[0].token_type, TokenType::DocComment);
+            assert_eq!(lex[0].value, " docs");
+            assert_eq!(lex[1].token_type, TokenType::Comment);
+            assert_eq!(lex[1].value, "// not docs");
+            assert_eq!(lex[2].token_type, TokenType::Comment);
+        }
+
+        #[test]
+        fn test_nested_block_comment() {
+            let input = "/* one /* two\n */ still one\n */ let ".to_string();
+            let lex = Token::lex(input);
+
+            assert_eq!(lex.len(), 2);
+            assert_eq!(lex[0].token_type, TokenType::Comment);
+            assert_eq!(lex[0].value, " one /* two\n */ still one\n ");
+            assert_eq!(lex[0].line, 1);
+            // Lines inside of the comment are counted
+            assert_eq!(lex[1].token_type, TokenType::Keyword(KeyWords::Let));
+            assert_eq!(lex[1].line, 3);
+        }
+
+        #[test]
+        fn test_unterminated_block_comment() {
+            let input = "let\n/* /* */ never closed".to_string();
+            let (lex, errors) = Token::lex_with_errors(input);
+
+            assert_eq!(lex[1].token_type, TokenType::Invalid);
+            assert_eq!(lex[1].line, 2);
+            assert_eq!(errors[0].kind, LexErrorKind::UnterminatedComment);
+            assert_eq!(errors[0].span.line, 2);
+        }
+    }
+
+    #[cfg(test)]
+    pub mod test_chars {
+        use crate::parser_lexer::lexer::{KeyWords, Lexer, Token, TokenType};
+
+        #[test]
+        fn test_char() {
+            let input = " 'a' '\\n' '\\'' char ".to_string();
+            let lex = Token::lex(input);
+
+            assert_eq!(lex[0].token_type, TokenType::Char);
+            assert_eq!(lex[0].value, "a");
+            assert_eq!(lex[1].token_type, TokenType::Char);
+            assert_eq!(lex[1].value, "\n");
+            assert_eq!(lex[2].value, "'");
+            assert_eq!(lex[3].token_type, TokenType::Keyword(KeyWords::Char));
+        }
+
+        #[test]
+        fn test_invalid_char() {
+            let lex = Token::lex(" '' ".to_string());
+            assert_eq!(lex[0].token_type, TokenType::Invalid);
+
+            let lex = Token::lex(" 'ab' ".to_string());
+            assert_eq!(lex[0].token_type, TokenType::Invalid);
+
+            // Chars have to fit in a single byte
+            let lex = Token::lex(" 'é' ".to_string());
+            assert_eq!(lex[0].token_type, TokenType::Invalid);
+        }
+    }
+
+    #[cfg(test)]
+    pub mod test_source_map {
+        use crate::parser_lexer::{
+            lexer::{Lexer, Token},
+            source_map::{file_name, SourceMap},
+        };
+
+        #[test]
+        fn test_source_map() {
+            let mut map = SourceMap::new();
+            let main = map.add("main.lp", "let a = 1;");
+            let lib = map.add("lib.lp", "let b = 2;");
+
+            assert_ne!(main, lib);
+            assert_eq!(map.name(main), Some("main.lp"));
+            assert_eq!(map.get(lib).unwrap().source, "let b = 2;");
+        }
+
+        #[test]
+        fn test_lex_file() {
+            let (lex, errors) = Token::lex_file("tokens.lp", "let a = 1;".to_string());
+
+            assert!(errors.is_empty());
+            let file = lex[0].file().unwrap();
+            assert!(lex.iter().all(|token| token.file() == Some(file)));
+            assert_eq!(file_name(file).as_deref(), Some("tokens.lp"));
+
+            // Tokens that weren't lexed from a file don't have one
+            let lex = Token::lex("let a = 1;".to_string());
+            assert_eq!(lex[0].file(), None);
+        }
+    }
+
+    #[cfg(test)]
+    pub mod test_symbols {
+        use crate::parser_lexer::{
+            lexer::{Lexer, Token},
+            symbol::Symbol,
+        };
+
+        #[test]
+        fn test_intern() {
+            let a = Symbol::intern("größe");
+            assert_eq!(a, Symbol::intern("größe"));
+            assert_ne!(a, Symbol::intern("grösse"));
+            assert_eq!(a.as_str(), "größe");
+            assert_eq!(a.to_string(), "größe");
+
+            assert!(Symbol::default().is_empty());
+            assert_eq!(Symbol::intern(""), Symbol::EMPTY);
+        }
+
+        #[test]
+        fn test_token_values_are_interned() {
+            let lex = Token::lex("let count = count;".to_string());
+
+            // The same identifier always ends up as the same symbol
+            assert_eq!(lex[1].value, lex[3].value);
+            assert_eq!(lex[1].value, "count");
+        }
+    }
+
+    #[cfg(test)]
+    pub mod test_structs {
+        use crate::parser_lexer::lexer::{KeyWords, Lexer, Token, TokenType};
+
+        #[test]
+        fn test_struct_tokens() {
+            let lex = Token::lex("struct Point { x: number } p.x".to_string());
+
+            assert_eq!(lex[0].token_type, TokenType::Keyword(KeyWords::Struct));
+            assert_eq!(lex[1].token_type, TokenType::Identifier);
+            assert_eq!(lex[3].value, "x");
+            assert_eq!(lex[4].token_type, TokenType::Colon);
+            assert_eq!(lex[7].value, "p");
+            assert_eq!(lex[8].token_type, TokenType::Dot);
+            assert_eq!(lex[9].value, "x");
+        }
     }
 }
@@ -1,11 +1,53 @@
 #[cfg(test)]
 pub mod test_parser {
     use crate::{
-        ast::ast::{AstVar, ReturnTypes, Type},
+        ast::ast::{AstVar, ReturnTypes, Type, TypesArg},
+        ast::expr::ExprKind,
         ast::variable::TypeVar,
-        parser_lexer::{parser::{Parse, Parser, WalkParser}, lexer::{KeyWords, Lexer, Operators, Token, TokenType}},
+        parser_lexer::{parser::{Parse, Parser, WalkParser}, lexer::{KeyWords, Lexer, Operators, Span, Token, TokenType}},
     };
 
+    /// Helpers shared by the test modules below
+    #[cfg(test)]
+    pub mod helpers {
+        use crate::{
+            ast::{
+                ast::{Ast, Type},
+                expr::Expr,
+                variable::TypeVar,
+            },
+            parser_lexer::{
+                lexer::{Lexer, Token},
+                parser::{Parse, Parser},
+            },
+        };
+
+        /// Parses the whole input, the input has to be valid
+        pub fn parse(input: &str) -> Ast {
+            Parser::new(Token::lex(input.to_string())).parse().unwrap()
+        }
+
+        /// Parses the input as the body of a main function and returns the statements in it
+        pub fn parse_body(input: &str) -> Vec<Ast> {
+            let parse = parse(&format!("fn main() {{ {input} }}"));
+            let Type::Function(func) = &parse.body[0].type_ else {
+                panic!("Expected a function found {:#?}", parse.body[0]);
+            };
+            func.body.as_ref().unwrap().body.clone()
+        }
+
+        /// The expression a variable is set to
+        pub fn var_expr(ast: &Ast) -> Expr {
+            match &ast.type_ {
+                Type::Variable(var) => match &var.type_ {
+                    TypeVar::Expr(expr) => *expr.clone(),
+                    value => panic!("Expected a expression found {:#?}", value),
+                },
+                token => panic!("Expected a variable found {:#?}", token),
+            }
+        }
+    }
+
     #[test]
     fn test_peak_nth_tokens() {
         let input = " if ==  else ".to_string();
@@ -18,7 +60,8 @@ pub mod test_parser {
             Some(Token {
                 token_type: TokenType::Keyword(KeyWords::Else),
                 value: "else".into(),
-                line: 1
+                line: 1,
+                span: Span::new(8, 12, 1, 9),
             })
         )
     }
@@ -36,12 +79,14 @@ pub mod test_parser {
                 Token {
                     token_type: TokenType::Keyword(KeyWords::If),
                     value: "if".into(),
-                    line: 1
+                    line: 1,
+                    span: Span::new(1, 3, 1, 2),
                 },
                 Token {
                     token_type: TokenType::Operator(Operators::EqEq),
                     value: "==".into(),
-                    line: 1
+                    line: 1,
+                    span: Span::new(4, 6, 1, 5),
                 },
             ])
         )
@@ -65,12 +110,14 @@ pub mod test_parser {
                 Token {
                     token_type: TokenType::Keyword(KeyWords::If),
                     value: "if".into(),
-                    line: 1
+                    line: 1,
+                    span: Span::new(1, 3, 1, 2),
                 },
                 Token {
                     token_type: TokenType::Operator(Operators::EqEq),
                     value: "==".into(),
-                    line: 1
+                    line: 1,
+                    span: Span::new(4, 6, 1, 5),
                 }
             ]
         );
@@ -93,6 +140,12 @@ pub mod test_parser {
         let var_2 = &parse.as_ref().unwrap().body[1];
         assert_eq!(var_2.var_name(), Some("wow".into()));
         assert_eq!(var_2.var_value(), Some(TypeVar::Number(20)));
+
+        // The span of a variable reaches from the let keyword up until the semicolon
+        match &var_2.type_ {
+            Type::Variable(var) => assert_eq!(var.span, Span::new(31, 44, 2, 1)),
+            token => panic!("Expected a variable found {:#?}", token),
+        }
     }
 
     #[test]
@@ -162,4 +215,1230 @@ pub mod test_parser {
             ),
         }
     }
+
+    #[test]
+    // Tests for block comments look at file: sample_code/testing/comments.lp for the code being
+    // tested
+    fn parsing_comments() {
+        let lexer = Token::lex(include_str!("../../sample_code/testing/comments.lp").to_string());
+        let parse = Parser::new(lexer).parse().unwrap();
+
+        // The commented out function should be skipped entirely
+        assert_eq!(parse.body.len(), 1);
+        let var = &parse.body[0];
+        assert_eq!(var.var_name(), Some("after".into()));
+        assert_eq!(var.var_value(), Some(TypeVar::Number(10)));
+        match &var.type_ {
+            Type::Variable(var) => assert_eq!(var.line, 8),
+            token => panic!("Expected a variable found {:#?}", token),
+        }
+    }
+
+    #[test]
+    // Tests for doc comments look at file: sample_code/testing/docs.lp for the code being tested
+    fn parsing_doc_comments() {
+        let lexer = Token::lex(include_str!("../../sample_code/testing/docs.lp").to_string());
+        let parse = Parser::new(lexer).parse().unwrap();
+
+        match &parse.body[0].type_ {
+            Type::ConstVar(var) => assert_eq!(var.docs, vec![" The answer to everything"]),
+            token => panic!("Expected a constant found {:#?}", token),
+        }
+        match &parse.body[1].type_ {
+            Type::ConstVar(var) => assert!(var.docs.is_empty()),
+            token => panic!("Expected a constant found {:#?}", token),
+        }
+        match &parse.body[2].type_ {
+            Type::Function(func) => {
+                assert_eq!(func.docs, vec![" Adds one to the number", " and returns it"]);
+                match &func.body.as_ref().unwrap().body[0].type_ {
+                    Type::Function(inner) => {
+                        assert_eq!(inner.docs, vec![" Nested functions get their own docs"])
+                    }
+                    token => panic!("Expected a function found {:#?}", token),
+                }
+            }
+            token => panic!("Expected a function found {:#?}", token),
+        }
+        match &parse.body[4].type_ {
+            Type::Function(func) => assert!(func.docs.is_empty()),
+            token => panic!("Expected a function found {:#?}", token),
+        }
+    }
+
+    #[test]
+    fn parsing_unterminated_comment() {
+        let lexer = Token::lex("let a = 1;\n/* no end".to_string());
+        let parse = Parser::new(lexer).parse();
+        assert!(parse.is_err());
+    }
+
+    #[test]
+    fn parsing_with_lex_errors() {
+        // The parser doesn't run on the tokens when the lexer found errors
+        let (lexer, errors) = Token::lex_with_errors("let a = \"no end;".to_string());
+        let parse = Parser::new(lexer).with_lex_errors(errors).parse();
+        let errors = parse.unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0]
+            .to_string()
+            .contains("Found a string without a closing quote"));
+    }
+
+    #[test]
+    // Tests for parsing chars look at file: sample_code/testing/chars.lp for the code being
+    // tested
+    fn parsing_chars() {
+        let lexer = Token::lex(include_str!("../../sample_code/testing/chars.lp").to_string());
+        let parse = Parser::new(lexer).parse().unwrap();
+
+        assert_eq!(parse.body[0].var_value(), Some(TypeVar::Char('a')));
+        assert_eq!(parse.body[1].var_value(), Some(TypeVar::Char('\n')));
+
+        match &parse.body[2].type_ {
+            Type::Function(func) => {
+                assert_eq!(func.return_type, ReturnTypes::Char);
+                assert_eq!(func.args[0].type_, TypesArg::Char);
+                assert_eq!(func.args[0].value, "c");
+                match &func.body.as_ref().unwrap().body[0].type_ {
+                    Type::Return(ret) => {
+                        assert_eq!(ret.expr.as_ref().unwrap().kind, ExprKind::Char('b'));
+                    }
+                    token => panic!("Expected a return found {:#?}", token),
+                }
+            }
+            token => panic!("Expected a function found {:#?}", token),
+        }
+    }
+
+    #[test]
+    fn parsing_numbers() {
+        let lexer = Token::lex("let a = 0x10; let b = 0b11; let c = 2_147_483_647;".to_string());
+        let parse = Parser::new(lexer).parse().unwrap();
+
+        assert_eq!(parse.body[0].var_value(), Some(TypeVar::Number(16)));
+        assert_eq!(parse.body[1].var_value(), Some(TypeVar::Number(3)));
+        assert_eq!(parse.body[2].var_value(), Some(TypeVar::Number(i32::MAX)));
+    }
+
+    #[test]
+    fn parsing_numbers_out_of_range() {
+        // These used to panic, they should now be reported as errors
+        let lexer = Token::lex("let a = 99999999999;".to_string());
+        assert!(Parser::new(lexer).parse().is_err());
+
+        let lexer = Token::lex("@printf(\"%d\", 0xFFFFFFFFF);".to_string());
+        assert!(Parser::new(lexer).parse().is_err());
+
+        let lexer = Token::lex("fn main() number { return 4294967296; }".to_string());
+        assert!(Parser::new(lexer).parse().is_err());
+    }
+
+    #[test]
+    fn parsing_number_args_and_arrays() {
+        let lexer = Token::lex("@printf(\"%d\", 0x1F); let arr = [1, 0b10, 3];".to_string());
+        let parse = Parser::new(lexer).parse().unwrap();
+
+        match &parse.body[0].type_ {
+            // Arguments are stored as plain decimal numbers
+            Type::FunctionCall(call) => assert_eq!(call.args[1].value, "31"),
+            token => panic!("Expected a function call found {:#?}", token),
+        }
+        assert_eq!(
+            parse.body[1].var_value(),
+            Some(TypeVar::Arr {
+                values: vec![TypeVar::Number(1), TypeVar::Number(2), TypeVar::Number(3)]
+            })
+        );
+    }
+
+    #[test]
+    fn parsing_error_file_name() {
+        let (lexer, _) = Token::lex_file("broken.lp", "let a = 1".to_string());
+        let errors = Parser::new(lexer).parse().unwrap_err();
+
+        assert!(errors[0].to_string().contains("broken.lp"));
+    }
+
+    #[cfg(test)]
+    pub mod test_expressions {
+        use crate::{
+            ast::{
+                ast::{AstVar, Case, Condition, Type},
+                expr::{BinOp, Expr, ExprKind, UnaryOp},
+                variable::TypeVar,
+            },
+            parser_lexer::{
+                lexer::{Lexer, Token},
+                parser::{Parse, Parser},
+            },
+        };
+
+        /// Writes the expression as a s-expression so only the shape of the tree gets compared
+        fn shape(expr: &Expr) -> String {
+            match &expr.kind {
+                ExprKind::Number(num) => num.to_string(),
+                ExprKind::Identifier(id) => id.to_string(),
+                ExprKind::Bool(bool) => bool.to_string(),
+                ExprKind::Call(call) => format!("{}()", call.name),
+                ExprKind::Binary { op, lhs, rhs } => {
+                    let op = match op {
+                        BinOp::Add => "+",
+                        BinOp::Sub => "-",
+                        BinOp::Mul => "*",
+                        BinOp::Div => "/",
+                        BinOp::Rem => "%",
+                        BinOp::And => "&&",
+                        BinOp::Or => "||",
+                        BinOp::Less => "<",
+                        BinOp::EqEq => "==",
+                        op => unimplemented!("{op:?}"),
+                    };
+                    format!("({op} {} {})", shape(lhs), shape(rhs))
+                }
+                ExprKind::Unary { op, expr } => match op {
+                    UnaryOp::Neg => format!("(- {})", shape(expr)),
+                    UnaryOp::Not => format!("(! {})", shape(expr)),
+                },
+                kind => unimplemented!("{kind:?}"),
+            }
+        }
+
+        fn parse_value(value: &str) -> TypeVar {
+            let lexer = Token::lex(format!("let a: number = {value};"));
+            let parse = Parser::new(lexer).parse().unwrap();
+            parse.body[0].var_value().unwrap()
+        }
+
+        fn parse_expr(value: &str) -> String {
+            match parse_value(value) {
+                TypeVar::Expr(expr) => shape(&expr),
+                value => panic!("Expected a expression found {:#?}", value),
+            }
+        }
+
+        #[test]
+        fn test_precedence() {
+            assert_eq!(parse_expr("1 + 2 * 3"), "(+ 1 (* 2 3))");
+            assert_eq!(parse_expr("1 * 2 + 3"), "(+ (* 1 2) 3)");
+            assert_eq!(parse_expr("a < b + 1 && c"), "(&& (< a (+ b 1)) c)");
+            assert_eq!(parse_expr("a || b && c"), "(|| a (&& b c))");
+        }
+
+        #[test]
+        fn test_associativity() {
+            assert_eq!(parse_expr("a - b - c"), "(- (- a b) c)");
+            assert_eq!(parse_expr("a / b % c"), "(% (/ a b) c)");
+        }
+
+        #[test]
+        fn test_parentheses_and_unary() {
+            assert_eq!(parse_expr("(1 + 2) * 3"), "(* (+ 1 2) 3)");
+            assert_eq!(parse_expr("-(a * 2)"), "(- (* a 2))");
+            assert_eq!(parse_expr("!a == false"), "(== (! a) false)");
+            assert_eq!(parse_expr("add(1, 2) * -b"), "(* add() (- b))");
+            assert!(Parser::new(Token::lex("let a = (1 + 2;".to_string())).parse().is_err());
+            assert!(Parser::new(Token::lex("let a = 1 + ;".to_string())).parse().is_err());
+        }
+
+        #[test]
+        fn test_simple_values() {
+            // Single values keep their own variant
+            assert_eq!(parse_value("-5"), TypeVar::Number(-5));
+            assert_eq!(parse_value("-2147483648"), TypeVar::Number(i32::MIN));
+            assert_eq!(parse_value("(b)"), TypeVar::Identifier("b".into()));
+        }
+
+        #[test]
+        fn test_expression_args_and_returns() {
+            let lexer = Token::lex(
+                "fn main() number { @printf(\"%d\", a + 1, 2); return a * (b + 1); }".to_string(),
+            );
+            let parse = Parser::new(lexer).parse().unwrap();
+            let Type::Function(func) = &parse.body[0].type_ else {
+                panic!("Expected a function found {:#?}", parse.body[0]);
+            };
+            let body = &func.body.as_ref().unwrap().body;
+
+            match &body[0].type_ {
+                Type::FunctionCall(call) => {
+                    assert_eq!(call.args.len(), 3);
+                    assert_eq!(call.args[0].value, "%d");
+                    assert_eq!(shape(call.args[1].expr.as_ref().unwrap()), "(+ a 1)");
+                    assert_eq!(call.args[2].value, "2");
+                }
+                token => panic!("Expected a function call found {:#?}", token),
+            }
+            match &body[1].type_ {
+                Type::Return(ret) => {
+                    assert_eq!(shape(ret.expr.as_ref().unwrap()), "(* a (+ b 1))");
+                }
+                token => panic!("Expected a return found {:#?}", token),
+            }
+        }
+
+        #[test]
+        fn test_case_operands() {
+            let lexer = Token::lex("fn main() { if a + 1 > b * 2 { } }".to_string());
+            let parse = Parser::new(lexer).parse().unwrap();
+            let Type::Function(func) = &parse.body[0].type_ else {
+                panic!("Expected a function found {:#?}", parse.body[0]);
+            };
+            match &func.body.as_ref().unwrap().body[0].type_ {
+                Type::Logic(logic) => match &logic.if_ {
+                    Condition::Case(Case::More(TypeVar::Expr(lhs), TypeVar::Expr(rhs))) => {
+                        assert_eq!(shape(lhs), "(+ a 1)");
+                        assert_eq!(shape(rhs), "(* b 2)");
+                    }
+                    case => panic!("Expected a more case found {:#?}", case),
+                },
+                token => panic!("Expected a if statement found {:#?}", token),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    pub mod test_conditions {
+        use crate::{
+            ast::{
+                ast::{Case, Condition, Type},
+                variable::TypeVar,
+            },
+            parser_lexer::{
+                lexer::{Lexer, Token},
+                parser::{Parse, Parser},
+            },
+        };
+
+        fn parse_condition(condition: &str) -> Condition {
+            let lexer = Token::lex(format!("fn main() {{ if {condition} {{ }} }}"));
+            let parse = Parser::new(lexer).parse().unwrap();
+            let Type::Function(func) = &parse.body[0].type_ else {
+                panic!("Expected a function found {:#?}", parse.body[0]);
+            };
+            match &func.body.as_ref().unwrap().body[0].type_ {
+                Type::Logic(logic) => logic.if_.clone(),
+                token => panic!("Expected a if statement found {:#?}", token),
+            }
+        }
+
+        fn id(name: &str) -> TypeVar {
+            TypeVar::Identifier(name.into())
+        }
+
+        #[test]
+        fn test_single_value() {
+            assert_eq!(parse_condition("flag"), Condition::Value(id("flag")));
+            assert_eq!(
+                parse_condition("!flag"),
+                Condition::Not(Box::new(Condition::Value(id("flag"))))
+            );
+        }
+
+        #[test]
+        fn test_connectives_are_kept() {
+            let more = Condition::Case(Case::More(id("a"), TypeVar::Number(1)));
+            assert_eq!(
+                parse_condition("a > 1 && b || !c"),
+                Condition::Or(
+                    Box::new(Condition::And(
+                        Box::new(more.clone()),
+                        Box::new(Condition::Value(id("b")))
+                    )),
+                    Box::new(Condition::Not(Box::new(Condition::Value(id("c")))))
+                )
+            );
+            assert_eq!(
+                parse_condition("a > 1 && (b || c)"),
+                Condition::And(
+                    Box::new(more),
+                    Box::new(Condition::Or(
+                        Box::new(Condition::Value(id("b"))),
+                        Box::new(Condition::Value(id("c")))
+                    ))
+                )
+            );
+        }
+
+        #[test]
+        fn test_condition_uses() {
+            let condition = parse_condition("a > 1 || !(b == c)");
+            assert!(condition.uses("a".into()));
+            assert!(condition.uses("c".into()));
+            assert!(!condition.uses("d".into()));
+        }
+
+        #[test]
+        fn test_assignment_is_not_a_condition() {
+            let lexer = Token::lex("fn main() { if a = 1 { } }".to_string());
+            assert!(Parser::new(lexer).parse().is_err());
+        }
+    }
+
+    #[cfg(test)]
+    pub mod test_else_if {
+        use crate::{
+            ast::ast::{Ast, Condition, Type},
+            lepa_analyzer::unused::{Unused, UnusedValues},
+            parser_lexer::{
+                lexer::{Lexer, Token},
+                parser::{Parse, Parser},
+            },
+        };
+
+        use super::helpers::parse;
+
+        #[test]
+        fn test_else_if_chain() {
+            let parse = parse(
+                "fn main() { if a == 1 { } else if a == 2 { } else if a == 3 { } else { let b = 1; } }",
+            );
+            let Type::Function(func) = &parse.body[0].type_ else {
+                panic!("Expected a function found {:#?}", parse.body[0]);
+            };
+            let Type::Logic(logic) = &func.body.as_ref().unwrap().body[0].type_ else {
+                panic!("Expected a if statement");
+            };
+
+            let second = logic.else_if().unwrap();
+            let third = second.else_if().unwrap();
+            assert!(matches!(third.if_, Condition::Case(_)));
+            assert!(third.else_if().is_none());
+            assert_eq!(logic.branches().len(), 4);
+            assert_eq!(logic.branches()[3].body.len(), 1);
+            // The span of the chain reaches up until the end of the last else
+            assert_eq!(logic.span.end, third.span.end);
+        }
+
+        #[test]
+        fn test_else_without_body() {
+            let lexer = Token::lex("fn main() { if a == 1 { } else a }".to_string());
+            assert!(Parser::new(lexer).parse().is_err());
+        }
+
+        #[test]
+        fn test_unused_in_else_if() {
+            let parse = parse(
+                "fn main() { let a = 1; let b = 2; let c = 3; if a == 1 { } else if b == 2 { let d = 4; } else { @printf(\"%d\", c); } }",
+            );
+            let unused: Vec<_> = Ast::find_unused(&parse)
+                .into_iter()
+                .filter_map(|unused| match unused {
+                    UnusedValues::Variable(var) => Some(var.name.to_string()),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(unused, vec!["d".to_string()]);
+        }
+
+        #[test]
+        fn test_calls_in_branches() {
+            let parse = parse(
+                "fn a() number { return 1; } fn b() number { return 1; } fn c() { } fn d() { } \
+                 fn main() { if a() == 1 { } else if b() { c(); } else { d(); } }",
+            );
+            let unused: Vec<_> = Ast::find_unused(&parse)
+                .into_iter()
+                .filter_map(|unused| match unused {
+                    UnusedValues::Function(func) => Some(func.name.to_string()),
+                    _ => None,
+                })
+                .collect();
+            assert!(unused.is_empty(), "{unused:#?}");
+        }
+    }
+
+    #[cfg(test)]
+    pub mod test_while {
+        use crate::{
+            ast::ast::{Ast, Case, Condition, Type},
+            ast::variable::TypeVar,
+            lepa_analyzer::unused::{Unused, UnusedValues},
+            parser_lexer::{
+                lexer::{Lexer, Token},
+                parser::{Parse, Parser},
+            },
+        };
+
+        use super::helpers::parse_body;
+
+        #[test]
+        fn test_while_loop() {
+            let body = parse_body("while i < 10 { if i == 5 { break; } continue; }");
+            let Type::While(while_) = &body[0].type_ else {
+                panic!("Expected a while loop found {:#?}", body[0]);
+            };
+            assert_eq!(
+                while_.condition,
+                Condition::Case(Case::Less(
+                    TypeVar::Identifier("i".into()),
+                    TypeVar::Number(10)
+                ))
+            );
+            assert_eq!(while_.body.body.len(), 2);
+            assert!(matches!(while_.body.body[1].type_, Type::Continue(_)));
+            let Type::Logic(logic) = &while_.body.body[0].type_ else {
+                panic!("Expected a if statement");
+            };
+            assert!(matches!(logic.do_.body[0].type_, Type::Break(_)));
+        }
+
+        #[test]
+        fn test_invalid_while() {
+            let lexer = Token::lex("fn main() { while a < 1 }".to_string());
+            assert!(Parser::new(lexer).parse().is_err());
+            let lexer = Token::lex("fn main() { while a < 1 { break } }".to_string());
+            assert!(Parser::new(lexer).parse().is_err());
+        }
+
+        #[test]
+        fn test_unused_in_while() {
+            let lexer = Token::lex(
+                "fn main() { let i = 0; let max = 10; while i < max { let unused = 1; } }"
+                    .to_string(),
+            );
+            let parse = Parser::new(lexer).parse().unwrap();
+            let unused: Vec<_> = Ast::find_unused(&parse)
+                .into_iter()
+                .filter_map(|unused| match unused {
+                    UnusedValues::Variable(var) => Some(var.name.to_string()),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(unused, vec!["unused".to_string()]);
+        }
+
+        #[test]
+        fn test_call_in_while_condition() {
+            let lexer = Token::lex(
+                "fn check() number { return 0; } fn main() { while check() { } }".to_string(),
+            );
+            let parse = Parser::new(lexer).parse().unwrap();
+            let unused = Ast::find_unused(&parse);
+            assert!(
+                !unused
+                    .iter()
+                    .any(|unused| matches!(unused, UnusedValues::Function(_))),
+                "{unused:#?}"
+            );
+        }
+    }
+
+    #[cfg(test)]
+    pub mod test_for {
+        use crate::{
+            ast::ast::{Ast, ForIter, Type},
+            ast::expr::ExprKind,
+            lepa_analyzer::unused::{Unused, UnusedValues},
+            parser_lexer::{
+                lexer::{Lexer, Token},
+                parser::{Parse, Parser},
+            },
+        };
+
+        use super::helpers::parse_body;
+
+        fn unused_vars(input: &str) -> Vec<String> {
+            let parse = Parser::new(Token::lex(input.to_string())).parse().unwrap();
+            Ast::find_unused(&parse)
+                .into_iter()
+                .filter_map(|unused| match unused {
+                    UnusedValues::Variable(var) => Some(var.name.to_string()),
+                    _ => None,
+                })
+                .collect()
+        }
+
+        #[test]
+        fn test_for_range() {
+            let body = parse_body("for i in 0..n + 1 { if i == 5 { continue; } }");
+            let Type::For(for_) = &body[0].type_ else {
+                panic!("Expected a for loop found {:#?}", body[0]);
+            };
+            assert_eq!(for_.var.as_str(), "i");
+            let ForIter::Range { start, end } = &for_.iter else {
+                panic!("Expected a range found {:#?}", for_.iter);
+            };
+            assert_eq!(start.kind, ExprKind::Number(0));
+            assert!(matches!(end.kind, ExprKind::Binary { .. }));
+            assert_eq!(for_.body.body.len(), 1);
+        }
+
+        #[test]
+        fn test_for_array() {
+            let body = parse_body("for x in arr { break; } for y in [1, 2, 3] { }");
+            let Type::For(for_) = &body[0].type_ else {
+                panic!("Expected a for loop found {:#?}", body[0]);
+            };
+            assert_eq!(for_.var.as_str(), "x");
+            assert!(matches!(
+                &for_.iter,
+                ForIter::Array(array) if array.kind == ExprKind::Identifier("arr".into())
+            ));
+            let Type::For(for_) = &body[1].type_ else {
+                panic!("Expected a for loop found {:#?}", body[1]);
+            };
+            assert!(matches!(
+                &for_.iter,
+                ForIter::Array(array) if matches!(&array.kind, ExprKind::Array(values) if values.len() == 3)
+            ));
+        }
+
+        #[test]
+        fn test_invalid_for() {
+            for input in [
+                "fn main() { for in 0..10 { } }",
+                "fn main() { for i 0..10 { } }",
+                "fn main() { for i in 0..10 }",
+                "fn main() { for i in 0.. { } }",
+            ] {
+                let lexer = Token::lex(input.to_string());
+                assert!(Parser::new(lexer).parse().is_err(), "{input}");
+            }
+        }
+
+        #[test]
+        fn test_unused_in_for() {
+            let unused = unused_vars(
+                "fn main() { let max = 10; let arr = [1, 2]; for i in 0..max { for x in arr { let unused: number = x; } } }",
+            );
+            assert_eq!(unused, vec!["unused".to_string()]);
+        }
+
+        #[test]
+        fn test_loop_variable_shadows() {
+            // Within the body i is the loop variable, so the i declared before the loop is unused
+            let unused =
+                unused_vars("fn main() { let i = 1; for i in 0..10 { @printf(\"%d\", i); } }");
+            assert_eq!(unused, vec!["i".to_string()]);
+        }
+    }
+
+    #[cfg(test)]
+    pub mod test_assign {
+        use crate::{
+            ast::ast::{Ast, Type},
+            ast::expr::{BinOp, ExprKind},
+            ast::variable::AssignTarget,
+            lepa_analyzer::unused::{Unused, UnusedValues},
+            parser_lexer::{
+                lexer::{Lexer, Token},
+                parser::{Parse, Parser},
+            },
+        };
+
+        use super::helpers::parse_body;
+
+        #[test]
+        fn test_assign() {
+            let body = parse_body("x = x + 1; x += 2; arr[i + 1] %= 3; print(x);");
+            let Type::Assign(assign) = &body[0].type_ else {
+                panic!("Expected a assignment found {:#?}", body[0]);
+            };
+            assert_eq!(assign.target, AssignTarget::Variable("x".into()));
+            assert_eq!(assign.op, None);
+            assert!(matches!(
+                assign.value.kind,
+                ExprKind::Binary { op: BinOp::Add, .. }
+            ));
+
+            let Type::Assign(assign) = &body[1].type_ else {
+                panic!("Expected a assignment found {:#?}", body[1]);
+            };
+            assert_eq!(assign.op, Some(BinOp::Add));
+            assert_eq!(assign.value.kind, ExprKind::Number(2));
+
+            let Type::Assign(assign) = &body[2].type_ else {
+                panic!("Expected a assignment found {:#?}", body[2]);
+            };
+            assert_eq!(assign.op, Some(BinOp::Rem));
+            let AssignTarget::Index { array, index } = &assign.target else {
+                panic!("Expected a index found {:#?}", assign.target);
+            };
+            assert_eq!(array.kind, ExprKind::Identifier("arr".into()));
+            assert!(matches!(index.kind, ExprKind::Binary { .. }));
+
+            // Calls are still calls
+            assert!(matches!(body[3].type_, Type::FunctionCall(_)));
+        }
+
+        #[test]
+        fn test_invalid_assign() {
+            for input in [
+                "fn main() { x == 1; }",
+                "fn main() { x <= 1; }",
+                "fn main() { x = 1 }",
+                "fn main() { x = ; }",
+                "fn main() { arr[0 = 1; }",
+            ] {
+                let lexer = Token::lex(input.to_string());
+                assert!(Parser::new(lexer).parse().is_err(), "{input}");
+            }
+        }
+
+        #[test]
+        fn test_unused_assign() {
+            // Only storing a value in a variable doesn't use it, a compound assignment does
+            let lexer = Token::lex(
+                "fn main() { let a = 1; a = 2; let b = 1; b += 1; let c = 1; a = c; }".to_string(),
+            );
+            let parse = Parser::new(lexer).parse().unwrap();
+            let unused: Vec<_> = Ast::find_unused(&parse)
+                .into_iter()
+                .filter_map(|unused| match unused {
+                    UnusedValues::Variable(var) => Some(var.name.to_string()),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(unused, vec!["a".to_string()]);
+        }
+    }
+
+    #[cfg(test)]
+    pub mod test_annotations {
+        use crate::{
+            ast::ast::{Type, TypesArg},
+            ast::variable::{TypeVar, Variable},
+            parser_lexer::{
+                lexer::{Lexer, Token},
+                parser::{Parse, Parser},
+            },
+        };
+
+        fn parse_var(input: &str) -> Variable {
+            let parse = Parser::new(Token::lex(input.to_string())).parse().unwrap();
+            match &parse.body[0].type_ {
+                Type::ConstVar(var) => var.clone(),
+                Type::Function(func) => match &func.body.as_ref().unwrap().body[0].type_ {
+                    Type::Variable(var) => var.clone(),
+                    other => panic!("Expected a variable found {:#?}", other),
+                },
+                other => panic!("Expected a variable found {:#?}", other),
+            }
+        }
+
+        #[test]
+        fn test_annotations() {
+            let var = parse_var("fn main() { let count: number = 0; }");
+            assert_eq!(var.annotation, Some(TypesArg::Number));
+            assert_eq!(var.type_, TypeVar::Number(0));
+
+            let var = parse_var("const NAME: string = \"x\";");
+            assert_eq!(var.annotation, Some(TypesArg::String));
+            assert_eq!(var.type_, TypeVar::String("x".to_string()));
+
+            // Identifiers and calls are checked once they are compiled
+            let var = parse_var("fn main() { let c: char = other(1); }");
+            assert_eq!(var.annotation, Some(TypesArg::Char));
+            assert!(matches!(var.type_, TypeVar::FunctionCall(_)));
+
+            let var = parse_var("fn main() { let plain = 1; }");
+            assert_eq!(var.annotation, None);
+
+            // The type of a identifier or call isn't known while parsing
+            for input in ["fn main() { let c = a; }", "fn main() { let c = f(); }"] {
+                let lexer = Token::lex(input.to_string());
+                let errors = Parser::new(lexer).parse().unwrap_err();
+                assert!(errors[0].to_string().contains("can't be inferred"), "{input}");
+            }
+        }
+
+        #[test]
+        fn test_annotation_without_value() {
+            let var = parse_var("fn main() { let count: number; }");
+            assert_eq!(var.type_, TypeVar::Number(0));
+            let var = parse_var("fn main() { let c: char; }");
+            assert_eq!(var.type_, TypeVar::Char('\0'));
+
+            let lexer = Token::lex("fn main() { let count; }".to_string());
+            assert!(Parser::new(lexer).parse().is_err());
+        }
+
+        #[test]
+        fn test_invalid_annotations() {
+            for input in [
+                "fn main() { let count: number = \"one\"; }",
+                "fn main() { let c: char = 1; }",
+                "const NAME: number = 'a';",
+                "fn main() { let count: thing = 1; }",
+                "fn main() { let count: = 1; }",
+            ] {
+                let lexer = Token::lex(input.to_string());
+                assert!(Parser::new(lexer).parse().is_err(), "{input}");
+            }
+        }
+    }
+
+    #[cfg(test)]
+    pub mod test_recovery {
+        use crate::{
+            ast::ast::{Ast, Type},
+            errors::error::ErrorBuilder,
+            parser_lexer::{
+                lexer::{Lexer, Token},
+                parser::{Parse, Parser},
+            },
+        };
+
+        fn parse_all(input: &str) -> (Ast, Vec<ErrorBuilder>) {
+            Parser::new(Token::lex(input.to_string())).parse_all()
+        }
+
+        fn function_body(ast: &Ast, index: usize) -> Vec<Ast> {
+            match &ast.body[index].type_ {
+                Type::Function(func) => func.body.as_ref().unwrap().body.clone(),
+                other => panic!("Expected a function found {:#?}", other),
+            }
+        }
+
+        #[test]
+        fn test_every_error_is_found() {
+            let (ast, errors) = parse_all(
+                "fn main() {
+                    let a = ;
+                    let b = 1;
+                    let c 2;
+                    @printf(\"%d\", b);
+                }",
+            );
+            assert_eq!(errors.len(), 2);
+            let body = function_body(&ast, 0);
+            assert_eq!(body.len(), 2);
+            assert!(matches!(body[0].type_, Type::Variable(_)));
+            assert!(matches!(body[1].type_, Type::FunctionCall(_)));
+        }
+
+        #[test]
+        fn test_recover_at_top_level() {
+            let (ast, errors) = parse_all(
+                "const A = ;
+                 ) ]
+                 fn main() { return 0; }
+                 const B = 2;",
+            );
+            assert_eq!(errors.len(), 2);
+            assert_eq!(ast.body.len(), 2);
+            assert!(matches!(ast.body[0].type_, Type::Function(_)));
+            assert!(matches!(ast.body[1].type_, Type::ConstVar(_)));
+        }
+
+        #[test]
+        fn test_broken_header_skips_block() {
+            let (ast, errors) = parse_all("fn f(1 2) { let x = 1; return x; } fn main() {}");
+            assert_eq!(errors.len(), 1, "{errors:#?}");
+            assert_eq!(ast.body.len(), 1);
+            assert!(matches!(ast.body[0].type_, Type::Function(_)));
+
+            let (ast, errors) = parse_all("fn main() { if a = 1 { let b = 1; } let c = 2; }");
+            assert_eq!(errors.len(), 1, "{errors:#?}");
+            let body = function_body(&ast, 0);
+            assert_eq!(body.len(), 1);
+            assert!(matches!(body[0].type_, Type::Variable(_)));
+        }
+
+        #[test]
+        fn test_missing_semicolon_keeps_block() {
+            // The closing bracket still ends the body of main
+            let (ast, errors) = parse_all("fn main() { let a = 1 } fn other() {}");
+            assert_eq!(errors.len(), 1);
+            assert_eq!(ast.body.len(), 2);
+            assert!(function_body(&ast, 0).is_empty());
+        }
+
+        #[test]
+        fn test_no_panics() {
+            for input in [
+                "fn main() { ; }",
+                "fn main() { else }",
+                "fn some(let a) {}",
+                "fn main() { if a == 1",
+                "}",
+            ] {
+                let (_, errors) = parse_all(input);
+                assert!(!errors.is_empty(), "{input}");
+                let lexer = Token::lex(input.to_string());
+                assert!(Parser::new(lexer).parse().is_err(), "{input}");
+            }
+        }
+
+        #[test]
+        fn test_no_errors() {
+            let (ast, errors) = parse_all("fn main() { let a = 1; }");
+            assert!(errors.is_empty());
+            assert_eq!(function_body(&ast, 0).len(), 1);
+        }
+    }
+
+    #[cfg(test)]
+    pub mod test_return {
+        use crate::{
+            ast::{
+                ast::{Return, Type},
+                expr::ExprKind,
+            },
+            parser_lexer::{
+                lexer::{Lexer, Token},
+                parser::{Parse, Parser},
+            },
+        };
+
+        fn parse_return(input: &str) -> Return {
+            let input = format!("fn main() number {{ {input} }}");
+            let parse = Parser::new(Token::lex(input)).parse().unwrap();
+            let Type::Function(func) = &parse.body[0].type_ else {
+                panic!("Expected a function found {:#?}", parse.body[0]);
+            };
+            match &func.body.as_ref().unwrap().body[0].type_ {
+                Type::Return(ret) => ret.clone(),
+                token => panic!("Expected a return found {:#?}", token),
+            }
+        }
+
+        #[test]
+        fn test_return_expressions() {
+            let ret = parse_return("return add(a, 1) * 2;");
+            assert!(matches!(ret.expr.unwrap().kind, ExprKind::Binary { .. }));
+
+            let ret = parse_return("return @add(a, b);");
+            match ret.expr.unwrap().kind {
+                ExprKind::Call(call) => assert_eq!(call.name.as_str(), "@add"),
+                kind => panic!("Expected a call found {:#?}", kind),
+            }
+
+            let ret = parse_return("return [1, 2, 3];");
+            assert!(matches!(ret.expr.unwrap().kind, ExprKind::Array(values) if values.len() == 3));
+
+            let ret = parse_return("return;");
+            assert_eq!(ret.expr, None);
+        }
+
+        #[test]
+        fn test_return_uses() {
+            let ret = parse_return("return other(a) + b;");
+            assert!(ret.uses("a".into()));
+            assert!(ret.uses("b".into()));
+            assert_eq!(ret.calls()[0].name.as_str(), "other");
+        }
+
+        #[test]
+        fn test_invalid_return() {
+            for input in ["return 1", "return let;", "return 1 +;"] {
+                let input = format!("fn main() number {{ {input} }}");
+                assert!(Parser::new(Token::lex(input.clone())).parse().is_err(), "{input}");
+            }
+        }
+    }
+
+    #[cfg(test)]
+    pub mod test_call_args {
+        use crate::{
+            ast::{ast::Type, expr::ExprKind, function::Func},
+            parser_lexer::{
+                lexer::{Lexer, Token},
+                parser::{Parse, Parser},
+            },
+        };
+
+        fn parse_call(input: &str) -> Func {
+            let input = format!("fn main() {{ {input} }}");
+            let parse = Parser::new(Token::lex(input)).parse().unwrap();
+            let Type::Function(func) = &parse.body[0].type_ else {
+                panic!("Expected a function found {:#?}", parse.body[0]);
+            };
+            match &func.body.as_ref().unwrap().body[0].type_ {
+                Type::FunctionCall(call) => call.clone(),
+                token => panic!("Expected a function call found {:#?}", token),
+            }
+        }
+
+        #[test]
+        fn test_nested_calls() {
+            let call = parse_call("f(g(1), h(x));");
+            assert_eq!(call.args.len(), 2);
+            let names: Vec<_> = call.arg_calls().iter().map(|call| call.name.as_str()).collect();
+            assert_eq!(names, ["g", "h"]);
+            assert!(call.args_uses("x".into()));
+        }
+
+        #[test]
+        fn test_every_argument_is_a_expression() {
+            let call = parse_call("f(a + 1, \"text\", 'c', b, 2);");
+            let kinds: Vec<_> = call
+                .args
+                .iter()
+                .map(|arg| arg.expr.as_ref().unwrap().kind.clone())
+                .collect();
+            assert!(matches!(kinds[0], ExprKind::Binary { .. }));
+            assert_eq!(kinds[1], ExprKind::String("text".to_string()));
+            assert_eq!(kinds[2], ExprKind::Char('c'));
+            assert!(matches!(kinds[3], ExprKind::Identifier(id) if id.as_str() == "b"));
+            assert_eq!(kinds[4], ExprKind::Number(2));
+        }
+
+        #[test]
+        fn test_invalid_call_args() {
+            for input in ["f(1,);", "f(1 2);", "f(1;", "f(let);"] {
+                let input = format!("fn main() {{ {input} }}");
+                assert!(Parser::new(Token::lex(input.clone())).parse().is_err(), "{input}");
+            }
+        }
+    }
+
+    #[cfg(test)]
+    pub mod test_index {
+        use crate::{
+            ast::{
+                ast::{Ast, Type},
+                expr::ExprKind,
+                variable::AssignTarget,
+            },
+            lepa_analyzer::unused::{Unused, UnusedValues},
+            parser_lexer::{
+                lexer::{Lexer, Token},
+                parser::{Parse, Parser},
+            },
+        };
+
+        use super::helpers::{parse_body, var_expr};
+
+        #[test]
+        fn test_index() {
+            let body = parse_body("let x = arr[i + 1];");
+            match var_expr(&body[0]).kind {
+                ExprKind::Index { array, index } => {
+                    assert!(matches!(array.kind, ExprKind::Identifier(id) if id.as_str() == "arr"));
+                    assert!(matches!(index.kind, ExprKind::Binary { .. }));
+                }
+                kind => panic!("Expected a index found {:#?}", kind),
+            }
+
+            // Indexes are values like any other so they can be calculated with
+            let body = parse_body("let x = arr[0] * arr[@len(arr) - 1];");
+            assert!(matches!(var_expr(&body[0]).kind, ExprKind::Binary { .. }));
+        }
+
+        #[test]
+        fn test_index_store() {
+            let body = parse_body("arr[arr[0]] = arr[1] + 1;");
+            let Type::Assign(assign) = &body[0].type_ else {
+                panic!("Expected a assignment found {:#?}", body[0]);
+            };
+            match &assign.target {
+                AssignTarget::Index { array, index } => {
+                    assert_eq!(array.kind, ExprKind::Identifier("arr".into()));
+                    assert!(matches!(index.kind, ExprKind::Index { .. }));
+                }
+                target => panic!("Expected a index found {:#?}", target),
+            }
+        }
+
+        #[test]
+        fn test_nested_index_store() {
+            let body = parse_body("grid[i][j] = 1; p.arr[i] = 2;");
+            let Type::Assign(assign) = &body[0].type_ else {
+                panic!("Expected a assignment found {:#?}", body[0]);
+            };
+            let AssignTarget::Index { array, index } = &assign.target else {
+                panic!("Expected a index found {:#?}", assign.target);
+            };
+            assert!(matches!(array.kind, ExprKind::Index { .. }));
+            assert_eq!(index.kind, ExprKind::Identifier("j".into()));
+            assert_eq!(assign.target.name().as_str(), "grid");
+
+            let Type::Assign(assign) = &body[1].type_ else {
+                panic!("Expected a assignment found {:#?}", body[1]);
+            };
+            let AssignTarget::Index { array, .. } = &assign.target else {
+                panic!("Expected a index found {:#?}", assign.target);
+            };
+            assert!(matches!(array.kind, ExprKind::Field { .. }));
+            assert_eq!(assign.target.name().as_str(), "p");
+            // The index is read, the array it is stored in isn't
+            assert!(assign.uses("i".into()));
+            assert!(!assign.uses("p".into()));
+        }
+
+        #[test]
+        fn test_invalid_index() {
+            for input in ["let x = arr[1;", "let x = arr[];", "arr[1 = 2;"] {
+                let input = format!("fn main() {{ {input} }}");
+                assert!(Parser::new(Token::lex(input.clone())).parse().is_err(), "{input}");
+            }
+        }
+
+        #[test]
+        fn test_index_uses() {
+            let input =
+                "fn main() { let arr = [1, 2]; let i = 0; let x = arr[i]; @printf(\"%d\", x); }";
+            let parse = Parser::new(Token::lex(input.to_string())).parse().unwrap();
+            let unused: Vec<_> = Ast::find_unused(&parse)
+                .into_iter()
+                .filter(|unused| matches!(unused, UnusedValues::Variable(_)))
+                .collect();
+            assert!(unused.is_empty(), "{unused:#?}");
+        }
+    }
+
+    #[cfg(test)]
+    pub mod test_structs {
+        use crate::{
+            ast::{
+                ast::{Ast, ReturnTypes, Type, TypesArg},
+                expr::{BinOp, ExprKind},
+                variable::{AssignTarget, TypeVar},
+            },
+            lepa_analyzer::unused::{Unused, UnusedValues},
+            parser_lexer::{
+                lexer::{Lexer, Token},
+                parser::{Parse, Parser},
+            },
+        };
+
+        use super::helpers::{parse, parse_body, var_expr};
+
+        #[test]
+        fn test_struct() {
+            let ast = parse(
+                "/// A point on the screen\n struct Point { x: number, y: number, label: string, }",
+            );
+            let Type::Struct(point) = &ast.body[0].type_ else {
+                panic!("Expected a struct found {:#?}", ast.body[0]);
+            };
+            assert_eq!(point.name.as_str(), "Point");
+            assert_eq!(point.docs, vec![" A point on the screen"]);
+            let fields: Vec<_> = point.fields.iter().map(|field| field.name.as_str()).collect();
+            assert_eq!(fields, ["x", "y", "label"]);
+            assert_eq!(point.fields[2].type_, TypesArg::String);
+            assert_eq!(point.field_index("y".into()), Some(1));
+            assert_eq!(point.field_index("z".into()), None);
+
+            // Fields can be other structs
+            let ast = parse("struct Line { start: Point, end: Point }");
+            let Type::Struct(line) = &ast.body[0].type_ else {
+                panic!("Expected a struct found {:#?}", ast.body[0]);
+            };
+            assert_eq!(line.fields[0].type_, TypesArg::Struct("Point".into()));
+        }
+
+        #[test]
+        fn test_struct_literal() {
+            let body = parse_body("let p = Point { y: a + 1, x: 2 };");
+            match var_expr(&body[0]).kind {
+                ExprKind::StructLit { name, fields } => {
+                    assert_eq!(name.as_str(), "Point");
+                    // The fields stay in the order they are written in
+                    assert_eq!(fields[0].0.as_str(), "y");
+                    assert!(matches!(fields[0].1.kind, ExprKind::Binary { .. }));
+                    assert_eq!(fields[1].1.kind, ExprKind::Number(2));
+                }
+                kind => panic!("Expected a struct literal found {:#?}", kind),
+            }
+
+            // A block after a identifier is still the body of the if statement
+            let body = parse_body("if a { b = 1; }");
+            assert!(matches!(body[0].type_, Type::Logic(_)));
+        }
+
+        #[test]
+        fn test_field_access() {
+            let body = parse_body("let x = lines[0].start.x + 1;");
+            let ExprKind::Binary { lhs, .. } = var_expr(&body[0]).kind else {
+                panic!("Expected a binary expression");
+            };
+            let ExprKind::Field { value, field } = lhs.kind else {
+                panic!("Expected a field found {:#?}", lhs);
+            };
+            assert_eq!(field.as_str(), "x");
+            let ExprKind::Field { value, field } = value.kind else {
+                panic!("Expected a field found {:#?}", value);
+            };
+            assert_eq!(field.as_str(), "start");
+            assert!(matches!(value.kind, ExprKind::Index { .. }));
+            assert_eq!(value.root(), Some("lines".into()));
+
+            // Fields of the value returned by a call
+            let body = parse_body("let x = origin().x;");
+            assert!(matches!(var_expr(&body[0]).kind, ExprKind::Field { .. }));
+        }
+
+        #[test]
+        fn test_field_assign() {
+            let body = parse_body("p.start.x = i;");
+            let Type::Assign(assign) = &body[0].type_ else {
+                panic!("Expected a assignment found {:#?}", body[0]);
+            };
+            match &assign.target {
+                AssignTarget::Field { value, field } => {
+                    assert_eq!(field.as_str(), "x");
+                    assert!(matches!(value.kind, ExprKind::Field { .. }));
+                }
+                target => panic!("Expected a field found {:#?}", target),
+            }
+            assert_eq!(assign.target.name().as_str(), "p");
+            assert!(assign.uses("i".into()));
+            // Only the field changes, p itself isn't read
+            assert!(!assign.uses("p".into()));
+
+            let body = parse_body("p.x += 1;");
+            let Type::Assign(assign) = &body[0].type_ else {
+                panic!("Expected a assignment found {:#?}", body[0]);
+            };
+            assert_eq!(assign.op, Some(BinOp::Add));
+            assert!(assign.uses("p".into()));
+        }
+
+        #[test]
+        fn test_struct_functions() {
+            let ast = parse("fn move(Point p, number by) Point { return p; }");
+            let Type::Function(func) = &ast.body[0].type_ else {
+                panic!("Expected a function found {:#?}", ast.body[0]);
+            };
+            assert_eq!(func.args[0].type_, TypesArg::Struct("Point".into()));
+            assert_eq!(func.args[0].value.as_str(), "p");
+            assert_eq!(func.args[1].type_, TypesArg::Number);
+            assert_eq!(func.return_type, ReturnTypes::Struct("Point".into()));
+
+            let body = parse_body("let p: Point;");
+            let Type::Variable(var) = &body[0].type_ else {
+                panic!("Expected a variable found {:#?}", body[0]);
+            };
+            assert_eq!(var.annotation, Some(TypesArg::Struct("Point".into())));
+            assert_eq!(var.type_, TypeVar::None);
+        }
+
+        #[test]
+        fn test_invalid_struct() {
+            for input in [
+                "struct Point {}",
+                "struct { x: number }",
+                "struct Point { x, y: number }",
+                "struct Point { x: number y: number }",
+                "struct Point { x: number, x: number }",
+                "fn main() { let p = Point { x: 1 y: 2 }; }",
+                "fn main() { let p = Point { x: 1, x: 2 }; }",
+                "fn main() { let x = p.; }",
+                "fn main() { let x = p.1; }",
+            ] {
+                assert!(Parser::new(Token::lex(input.to_string())).parse().is_err(), "{input}");
+            }
+
+            // A broken struct is skipped as a whole, the function after it still gets parsed
+            let input = "struct Point { x, y: number } fn main() { let a = 1; }";
+            let (ast, errors) = Parser::new(Token::lex(input.to_string())).parse_all();
+            assert_eq!(errors.len(), 1, "{errors:#?}");
+            assert!(matches!(ast.body[0].type_, Type::Function(_)));
+        }
+
+        #[test]
+        fn test_struct_uses() {
+            let input = "struct Point { x: number } fn main() { let a = 1; let p = Point { x: a }; \
+                         @printf(\"%d\", p.x); }";
+            let ast = parse(input);
+            let unused: Vec<_> = Ast::find_unused(&ast)
+                .into_iter()
+                .filter(|unused| matches!(unused, UnusedValues::Variable(_)))
+                .collect();
+            assert!(unused.is_empty(), "{unused:#?}");
+        }
+    }
 }